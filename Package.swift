@@ -24,7 +24,12 @@ let package = Package(
         // Targets can depend on other targets in this package, and on products in packages this package depends on.
         .target(
             name: "SwiftRs",
+            dependencies: ["SwiftRsC"],
+            path: "src-swift"),
+        // C types that @_cdecl functions can use, re-exported by SwiftRs
+        .target(
+            name: "SwiftRsC",
             dependencies: [],
-            path: "src-swift")
+            path: "src-c")
     ]
 )
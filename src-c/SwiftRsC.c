@@ -0,0 +1,3 @@
+// SwiftPM needs at least one source file to build a C target,
+// everything else is in the header.
+#include "SwiftRsC.h"
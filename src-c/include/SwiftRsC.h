@@ -0,0 +1,33 @@
+#ifndef SWIFT_RS_C_H
+#define SWIFT_RS_C_H
+
+#include <stdbool.h>
+#include <stdint.h>
+
+// Optional scalars, which Rust receives as `Option<T>`.
+// Swift optionals don't have a C representation,
+// so these are passed as a tag followed by the value.
+
+#define SR_OPTIONAL(name, type) \
+    typedef struct {            \
+        bool has_value;         \
+        type value;             \
+    } SROptional##name
+
+SR_OPTIONAL(Bool, bool);
+SR_OPTIONAL(Int, intptr_t);
+SR_OPTIONAL(Int8, int8_t);
+SR_OPTIONAL(Int16, int16_t);
+SR_OPTIONAL(Int32, int32_t);
+SR_OPTIONAL(Int64, int64_t);
+SR_OPTIONAL(UInt, uintptr_t);
+SR_OPTIONAL(UInt8, uint8_t);
+SR_OPTIONAL(UInt16, uint16_t);
+SR_OPTIONAL(UInt32, uint32_t);
+SR_OPTIONAL(UInt64, uint64_t);
+SR_OPTIONAL(Float, float);
+SR_OPTIONAL(Double, double);
+
+#undef SR_OPTIONAL
+
+#endif
@@ -2,6 +2,8 @@
 /// to [`@autoreleasepool`](https://developer.apple.com/library/archive/documentation/Cocoa/Conceptual/MemoryMgmt/Articles/mmAutoreleasePools.html)
 /// in Objective-C
 ///
+/// On platforms without an Objective-C runtime, such as Linux, there are no autorelease pools
+/// and this just evaluates the block.
 ///
 /// ```no_run
 /// use swift_rs::autoreleasepool;
@@ -13,14 +15,19 @@
 #[macro_export]
 macro_rules! autoreleasepool {
     ( $expr:expr ) => {{
+        #[cfg(target_vendor = "apple")]
         extern "C" {
             fn objc_autoreleasePoolPush() -> *mut std::ffi::c_void;
             fn objc_autoreleasePoolPop(context: *mut std::ffi::c_void);
         }
 
+        #[cfg(target_vendor = "apple")]
         let pool = unsafe { objc_autoreleasePoolPush() };
         let r = { $expr };
-        unsafe { objc_autoreleasePoolPop(pool) };
+        #[cfg(target_vendor = "apple")]
+        unsafe {
+            objc_autoreleasePoolPop(pool)
+        };
         r
     }};
 }
@@ -1,8 +1,14 @@
 #![allow(dead_code)]
-use std::{env, fmt::Display, path::Path, path::PathBuf, process::Command};
+use std::{
+    collections::HashSet, env, fmt::Display, fs, io, path::Path, path::PathBuf, process::Command,
+};
 
 use serde::Deserialize;
 
+mod bindings;
+mod classes;
+mod layout;
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct SwiftTarget {
@@ -33,8 +39,8 @@ impl SwiftEnv {
         minimum_macos_version: &str,
         minimum_ios_version: Option<&str>,
         minimum_visionos_version: Option<&str>,
-    ) -> Self {
-        let rust_target = RustTarget::from_env();
+    ) -> Result<Self, SwiftLinkError> {
+        let rust_target = RustTarget::from_env()?;
         let target = rust_target.swift_target_triple(
             minimum_macos_version,
             minimum_ios_version,
@@ -44,10 +50,13 @@ impl SwiftEnv {
         let swift_target_info_str = Command::new("swift")
             .args(["-target", &target, "-print-target-info"])
             .output()
-            .unwrap()
+            .map_err(|source| SwiftLinkError::MissingToolchain {
+                tool: "swift".to_string(),
+                source,
+            })?
             .stdout;
 
-        serde_json::from_slice(&swift_target_info_str).unwrap()
+        serde_json::from_slice(&swift_target_info_str).map_err(SwiftLinkError::InvalidTargetInfo)
     }
 }
 
@@ -56,16 +65,18 @@ enum RustTargetOS {
     MacOS,
     IOS,
     VisionOS,
+    Linux,
 }
 
 impl RustTargetOS {
-    fn from_env() -> Self {
-        match env::var("CARGO_CFG_TARGET_OS").unwrap().as_str() {
+    fn from_env() -> Result<Self, SwiftLinkError> {
+        Ok(match env::var("CARGO_CFG_TARGET_OS").unwrap().as_str() {
             "macos" => RustTargetOS::MacOS,
             "ios" => RustTargetOS::IOS,
             "visionos" => RustTargetOS::VisionOS,
-            _ => panic!("unexpected target operating system"),
-        }
+            "linux" => RustTargetOS::Linux,
+            os => return Err(SwiftLinkError::UnsupportedTarget(os.to_string())),
+        })
     }
 
     fn to_swift(&self) -> &'static str {
@@ -73,6 +84,7 @@ impl RustTargetOS {
             Self::MacOS => "macosx",
             Self::IOS => "ios",
             Self::VisionOS => "xros",
+            Self::Linux => "linux-gnu",
         }
     }
 }
@@ -83,6 +95,7 @@ impl Display for RustTargetOS {
             Self::MacOS => write!(f, "macos"),
             Self::IOS => write!(f, "ios"),
             Self::VisionOS => write!(f, "visionos"),
+            Self::Linux => write!(f, "linux"),
         }
     }
 }
@@ -97,19 +110,22 @@ enum SwiftSDK {
 }
 
 impl SwiftSDK {
-    fn from_os(os: &RustTargetOS) -> Self {
+    /// Apple SDK for the target OS, or `None` on platforms
+    /// that build against a plain swift.org toolchain (Linux).
+    fn from_os(os: &RustTargetOS) -> Option<Self> {
         let target = env::var("TARGET").unwrap();
         let simulator = target.ends_with("ios-sim")
             || target.ends_with("visionos-sim")
             || (target.starts_with("x86_64") && target.ends_with("ios"));
 
-        match os {
+        Some(match os {
             RustTargetOS::MacOS => Self::MacOS,
             RustTargetOS::IOS if simulator => Self::IOSSimulator,
             RustTargetOS::IOS => Self::IOS,
             RustTargetOS::VisionOS if simulator => Self::VisionOSSimulator,
             RustTargetOS::VisionOS => Self::VisionOS,
-        }
+            RustTargetOS::Linux => return None,
+        })
     }
 
     fn clang_lib_extension(&self) -> &'static str {
@@ -138,16 +154,16 @@ impl Display for SwiftSDK {
 struct RustTarget {
     arch: String,
     os: RustTargetOS,
-    sdk: SwiftSDK,
+    sdk: Option<SwiftSDK>,
 }
 
 impl RustTarget {
-    fn from_env() -> Self {
+    fn from_env() -> Result<Self, SwiftLinkError> {
         let arch = env::var("CARGO_CFG_TARGET_ARCH").unwrap();
-        let os = RustTargetOS::from_env();
+        let os = RustTargetOS::from_env()?;
         let sdk = SwiftSDK::from_os(&os);
 
-        Self { arch, os, sdk }
+        Ok(Self { arch, os, sdk })
     }
 
     fn swift_target_triple(
@@ -163,11 +179,12 @@ impl RustTarget {
                 RustTargetOS::MacOS => minimum_macos_version,
                 RustTargetOS::IOS => minimum_ios_version.unwrap(),
                 RustTargetOS::VisionOS => minimum_visionos_version.unwrap(),
+                RustTargetOS::Linux => "",
             },
             // simulator suffix
             matches!(
                 self.sdk,
-                SwiftSDK::IOSSimulator | SwiftSDK::VisionOSSimulator
+                Some(SwiftSDK::IOSSimulator | SwiftSDK::VisionOSSimulator)
             )
             .then(|| "-simulator".to_string())
             .unwrap_or_default()
@@ -175,14 +192,18 @@ impl RustTarget {
     }
 
     fn unversioned_swift_target_triple(&self) -> String {
-        format!(
-            "{}-apple-{}",
-            match self.arch.as_str() {
-                "aarch64" => "arm64",
-                a => a,
-            },
-            self.os.to_swift(),
-        )
+        match self.os {
+            // swift.org toolchains use the same arch names as Rust on Linux
+            RustTargetOS::Linux => format!("{}-unknown-{}", self.arch, self.os.to_swift()),
+            _ => format!(
+                "{}-apple-{}",
+                match self.arch.as_str() {
+                    "aarch64" => "arm64",
+                    a => a,
+                },
+                self.os.to_swift(),
+            ),
+        }
     }
 }
 
@@ -191,6 +212,110 @@ struct SwiftPackage {
     path: PathBuf,
 }
 
+/// Reasons that [`SwiftLinker::try_link`] can fail.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SwiftLinkError {
+    /// The Rust target isn't one that Swift packages can be built for.
+    UnsupportedTarget(String),
+    /// A required tool, such as `swift` or `xcrun`, couldn't be run.
+    MissingToolchain { tool: String, source: io::Error },
+    /// `swift -print-target-info` didn't output the expected JSON.
+    InvalidTargetInfo(serde_json::Error),
+    /// The SDK or clang runtime library paths couldn't be found.
+    SdkLookup(String),
+    /// `swift build` failed for a package.
+    /// SwiftPM prints compiler diagnostics to stdout, so both streams are kept.
+    PackageCompile {
+        package: String,
+        stdout: String,
+        stderr: String,
+    },
+    /// A package built successfully but its static archive wasn't where it was expected.
+    MissingArchive { package: String, path: PathBuf },
+    /// The bindings requested with [`SwiftLinker::with_bindings`] couldn't be written.
+    WriteBindings { path: PathBuf, source: io::Error },
+    /// The classes requested with [`SwiftLinker::with_swift_classes`] couldn't be generated.
+    GenerateClasses { path: PathBuf, reason: String },
+    /// The layouts of Swift classes requested with [`SwiftLinker::with_layout_checks`]
+    /// couldn't be measured.
+    LayoutProbe(String),
+}
+
+impl Display for SwiftLinkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedTarget(os) => {
+                write!(f, "Swift packages can't be built for target OS `{os}`")
+            }
+            Self::MissingToolchain { tool, source } => {
+                write!(
+                    f,
+                    "Failed to run `{tool}`, is the Swift toolchain installed? ({source})"
+                )
+            }
+            Self::InvalidTargetInfo(e) => {
+                write!(
+                    f,
+                    "Failed to parse output of `swift -print-target-info`: {e}"
+                )
+            }
+            Self::SdkLookup(message) => write!(f, "{message}"),
+            Self::PackageCompile {
+                package,
+                stdout,
+                stderr,
+            } => {
+                write!(f, "Failed to compile swift package {package}:")?;
+                for output in [stdout, stderr] {
+                    if !output.trim().is_empty() {
+                        write!(f, "\n{}", output.trim_end())?;
+                    }
+                }
+                Ok(())
+            }
+            Self::MissingArchive { package, path } => write!(
+                f,
+                "Swift package {package} was built but {} doesn't exist",
+                path.display()
+            ),
+            Self::WriteBindings { path, source } => write!(
+                f,
+                "Failed to write Swift bindings to {}: {source}",
+                path.display()
+            ),
+            Self::GenerateClasses { path, reason } => write!(
+                f,
+                "Failed to generate Swift classes from {}: {reason}",
+                path.display()
+            ),
+            Self::LayoutProbe(reason) => {
+                write!(f, "Failed to check the layouts of Swift classes: {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SwiftLinkError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::MissingToolchain { source, .. } => Some(source),
+            Self::InvalidTargetInfo(e) => Some(e),
+            Self::WriteBindings { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Details of a successful [`SwiftLinker::try_link`].
+#[derive(Debug)]
+pub struct LinkOutput {
+    /// Static archives that were built and linked, in the order their packages were added.
+    pub archives: Vec<PathBuf>,
+    /// Directories the Swift runtime is linked from.
+    pub runtime_library_paths: Vec<PathBuf>,
+}
+
 /// Builder for linking the Swift runtime and custom packages.
 #[cfg(feature = "build")]
 pub struct SwiftLinker {
@@ -198,18 +323,25 @@ pub struct SwiftLinker {
     macos_min_version: String,
     ios_min_version: Option<String>,
     visionos_min_version: Option<String>,
+    bindings: Option<PathBuf>,
+    classes: Vec<(PathBuf, PathBuf)>,
+    layout_checks: bool,
 }
 
 impl SwiftLinker {
     /// Creates a new [`SwiftLinker`] with a minimum macOS verison.
     ///
     /// Minimum macOS version must be at least 10.13.
+    /// It is ignored when building for Linux.
     pub fn new(macos_min_version: &str) -> Self {
         Self {
             packages: vec![],
             macos_min_version: macos_min_version.to_string(),
             ios_min_version: None,
             visionos_min_version: None,
+            bindings: None,
+            classes: vec![],
+            layout_checks: false,
         }
     }
 
@@ -244,174 +376,791 @@ impl SwiftLinker {
         self
     }
 
+    /// Generates [`swift!`](crate::swift) declarations for the `@_cdecl` functions
+    /// in each package's library targets, writing them to `file_name` in `OUT_DIR`
+    /// so that they can be included in your crate:
+    ///
+    /// ```ignore
+    /// include!(concat!(env!("OUT_DIR"), "/swift_bindings.rs"));
+    /// ```
+    ///
+    /// Parameter and return types are mapped onto their `swift-rs` equivalents,
+    /// with classes declared in the packages mapped to `SRObject`s of a Rust struct
+    /// with the same name, which must be in scope where the file is included.
+    /// Since Swift's `SRObjectArray` doesn't say what it contains,
+    /// it's mapped to `SRObjectArray<()>`.
+    /// Untyped pointers must be followed by a comment naming the type they point to,
+    /// such as `UnsafeRawPointer /* SRString */`.
+    ///
+    /// Functions using types that can't be mapped are skipped with a warning
+    /// and need to be declared by hand. These are unannotated pointers, `SRClosure`,
+    /// `SREnum`, `SRResult`, `SRTask`, `SRWeak` and `SRMutableBuffer`.
+    pub fn with_bindings(mut self, file_name: impl AsRef<Path>) -> Self {
+        self.bindings = Some(file_name.as_ref().into());
+        self
+    }
+
+    /// Generates a Swift class for each `#[repr(C)]` struct in the Rust file `rust_source`,
+    /// writing them to `swift_output` before any packages are built.
+    /// Both paths are relative to your crate's root,
+    /// and `swift_output` should be inside one of your package's targets.
+    ///
+    /// Each class is an `NSObject` subclass with the same name as its struct,
+    /// and a stored property and initializer parameter for each field, in the same order.
+    /// Fields can be scalars, `SRString`, `SRData`, `SRArray<T>`, `SRObjectArray<T>`,
+    /// `SRObject<T>` of another class, or an `Option` of any of these objects.
+    ///
+    /// Structs with other fields are skipped with a warning.
+    pub fn with_swift_classes(
+        mut self,
+        rust_source: impl AsRef<Path>,
+        swift_output: impl AsRef<Path>,
+    ) -> Self {
+        self.classes
+            .push((rust_source.as_ref().into(), swift_output.as_ref().into()));
+        self
+    }
+
+    /// Measures the stored properties of every class declared in the packages,
+    /// so that `#[derive(SwiftLayout)]` can check that Rust structs match them.
+    ///
+    /// This builds and runs a small Swift program, so classes can only be measured
+    /// when the target is the same as the host.
+    /// When cross-compiling the checks are skipped with a warning.
+    pub fn with_layout_checks(mut self) -> Self {
+        self.layout_checks = true;
+        self
+    }
+
     /// Links the Swift runtime, then builds and links the provided packages.
-    /// This does not (yet) automatically rebuild your Swift files when they are modified,
-    /// you'll need to modify/save your `build.rs` file for that.
+    ///
+    /// Each package's manifest and the files in its targets,
+    /// including those of local path dependencies, are tracked for changes
+    /// so that modifying them causes the package to be rebuilt.
+    ///
+    /// # Panics
+    /// Panics if anything goes wrong, see [`try_link`](Self::try_link) for a fallible version.
     pub fn link(self) {
+        if let Err(e) = self.try_link() {
+            panic!("{e}");
+        }
+    }
+
+    /// Same as [`link`](Self::link), but returns an error instead of panicking.
+    ///
+    /// No linker instructions are given to Cargo unless every package builds successfully,
+    /// so build scripts can fall back to building without Swift:
+    ///
+    /// ```no_run
+    /// use swift_rs::{SwiftLinkError, SwiftLinker};
+    ///
+    /// match SwiftLinker::new("10.15").with_package("swift-lib", "./swift-lib/").try_link() {
+    ///     Ok(_) => println!("cargo:rustc-cfg=has_swift"),
+    ///     Err(e @ SwiftLinkError::MissingToolchain { .. }) => {
+    ///         println!("cargo:warning=Building without Swift features: {e}")
+    ///     }
+    ///     Err(e) => panic!("{e}"),
+    /// }
+    /// ```
+    pub fn try_link(self) -> Result<LinkOutput, SwiftLinkError> {
         let swift_env = SwiftEnv::new(
             &self.macos_min_version,
             self.ios_min_version.as_deref(),
             self.visionos_min_version.as_deref(),
-        );
+        )?;
+
+        let debug = env::var("DEBUG").unwrap() == "true";
+        let configuration = if debug { "debug" } else { "release" };
+        let rust_target = RustTarget::from_env()?;
+
+        let clang_rt = match &rust_target.sdk {
+            Some(sdk) => Some((sdk.clang_lib_extension(), clang_link_search_path()?)),
+            None => None,
+        };
+
+        let class_warnings = self.write_classes()?;
+
+        let mut archives = vec![];
+
+        for package in &self.packages {
+            let out_path = Path::new(&env::var("OUT_DIR").unwrap())
+                .join("swift-rs")
+                .join(&package.name);
+
+            let search_path = match &rust_target.sdk {
+                Some(sdk) => {
+                    self.build_apple_package(package, &rust_target, sdk, &out_path, configuration)?
+                }
+                None => build_linux_package(package, &rust_target, &out_path, configuration)?,
+            };
+
+            let archive = search_path.join(format!("lib{}.a", package.name));
+            if !archive.exists() {
+                return Err(SwiftLinkError::MissingArchive {
+                    package: package.name.clone(),
+                    path: archive,
+                });
+            }
+
+            archives.push(archive);
+        }
+
+        let binding_warnings = match &self.bindings {
+            Some(file_name) => self.write_bindings(file_name)?,
+            None => vec![],
+        };
+        let layout_warnings = if self.layout_checks {
+            self.write_layouts(&swift_env, &rust_target, &archives)?
+        } else {
+            vec![]
+        };
+        for warning in class_warnings
+            .into_iter()
+            .chain(binding_warnings)
+            .chain(layout_warnings)
+        {
+            println!("cargo:warning={warning}");
+        }
+        for (rust_source, _) in &self.classes {
+            println!("cargo:rerun-if-changed={}", rust_source.display());
+        }
 
         #[allow(clippy::uninlined_format_args)]
-        for path in swift_env.paths.runtime_library_paths {
+        for path in &swift_env.paths.runtime_library_paths {
             println!("cargo:rustc-link-search=native={path}");
         }
 
-        let debug = env::var("DEBUG").unwrap() == "true";
-        let configuration = if debug { "debug" } else { "release" };
-        let rust_target = RustTarget::from_env();
+        if let Some((extension, search_path)) = clang_rt {
+            println!("cargo:rustc-link-lib=clang_rt.{extension}");
+            println!("cargo:rustc-link-search={search_path}");
+        }
 
-        link_clang_rt(&rust_target);
+        let mut tracked_packages = HashSet::new();
 
-        for package in self.packages {
+        for (package, archive) in self.packages.iter().zip(&archives) {
             let package_path =
                 Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join(&package.path);
-            let out_path = Path::new(&env::var("OUT_DIR").unwrap())
-                .join("swift-rs")
-                .join(&package.name);
 
-            let sdk_path_output = Command::new("xcrun")
-                .args(["--sdk", &rust_target.sdk.to_string(), "--show-sdk-path"])
-                .output()
-                .unwrap();
-            if !sdk_path_output.status.success() {
-                panic!(
-                    "Failed to get SDK path with `xcrun --sdk {} --show-sdk-path`",
-                    rust_target.sdk
-                );
+            for input in package_inputs(&package_path, &mut tracked_packages) {
+                println!("cargo:rerun-if-changed={}", input.display());
             }
+            println!(
+                "cargo:rustc-link-search=native={}",
+                archive.parent().unwrap().display()
+            );
+            println!("cargo:rustc-link-lib=static={}", package.name);
+        }
 
-            let sdk_path = String::from_utf8_lossy(&sdk_path_output.stdout);
+        if rust_target.sdk.is_none() {
+            // Emitted after the packages so that the static archives
+            // come first on the linker's command line.
+            link_linux_runtime(&swift_env, &archives, debug);
+        }
+
+        Ok(LinkOutput {
+            archives,
+            runtime_library_paths: swift_env
+                .paths
+                .runtime_library_paths
+                .iter()
+                .map(PathBuf::from)
+                .collect(),
+        })
+    }
 
-            let mut command = Command::new("swift");
-            command.current_dir(&package.path);
+    /// Generates the Swift classes for each source file passed to
+    /// [`with_swift_classes`](Self::with_swift_classes),
+    /// returning warnings for any structs that were skipped.
+    fn write_classes(&self) -> Result<Vec<String>, SwiftLinkError> {
+        let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+        let mut warnings = vec![];
 
-            let arch = match std::env::consts::ARCH {
-                "aarch64" => "arm64",
-                arch => arch,
+        for (rust_source, swift_output) in &self.classes {
+            let error = |reason: String| SwiftLinkError::GenerateClasses {
+                path: rust_source.clone(),
+                reason,
             };
 
-            let mut swift_target_triple = rust_target.swift_target_triple(
-                &self.macos_min_version,
-                self.ios_min_version.as_deref(),
-                self.visionos_min_version.as_deref(),
+            let source = fs::read_to_string(manifest_dir.join(rust_source))
+                .map_err(|e| error(e.to_string()))?;
+            let (swift, skipped) =
+                classes::generate(&source, &rust_source.to_string_lossy()).map_err(error)?;
+            warnings.extend(skipped);
+
+            // Only write when something changed,
+            // otherwise the package would be rebuilt on every build
+            let swift_output = manifest_dir.join(swift_output);
+            if fs::read_to_string(&swift_output).ok().as_deref() != Some(swift.as_str()) {
+                fs::write(&swift_output, swift).map_err(|e| error(e.to_string()))?;
+            }
+        }
+
+        Ok(warnings)
+    }
+
+    /// Measures the classes in every package
+    /// and writes their layouts to `OUT_DIR` for `#[derive(SwiftLayout)]`.
+    fn write_layouts(
+        &self,
+        swift_env: &SwiftEnv,
+        rust_target: &RustTarget,
+        archives: &[PathBuf],
+    ) -> Result<Vec<String>, SwiftLinkError> {
+        let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+        let out_dir = Path::new(&env::var("OUT_DIR").unwrap()).join("swift-rs");
+        let layouts_dir = out_dir.join("layouts");
+        let io_error = |e: io::Error| SwiftLinkError::LayoutProbe(e.to_string());
+
+        // Classes that no longer exist shouldn't keep passing the check
+        let _ = fs::remove_dir_all(&layouts_dir);
+        fs::create_dir_all(&layouts_dir).map_err(io_error)?;
+
+        let can_run = env::var("HOST").ok() == env::var("TARGET").ok();
+        let mut warnings = vec![];
+        if !can_run {
+            warnings.push(
+                "Swift class layouts can't be checked when cross-compiling, skipping".to_string(),
             );
+        }
 
-            // Xcode 27's SwiftPM appends the host -sdk/-target after the -Xswiftc
-            // overrides below, so cross builds compile against the host SDK. Pass
-            // --triple there instead. macOS (host == target) is unaffected and
-            // keeps the legacy path.
-            let xcode27 = xcode_major_version().map(|v| v >= 27).unwrap_or(false);
-            let cross_compiling = !matches!(rust_target.os, RustTargetOS::MacOS);
-            let use_triple = cross_compiling && xcode27;
-
-            // Xcode 27 SDKs reject iOS deployment targets below 15
-            // ("supported deployment target versions is 15.0 to 27.0.x") and
-            // consumers commonly pass lower minimums (tauri passes ios13.0).
-            if xcode27 {
-                clamp_ios_deployment_target(&mut swift_target_triple, 15);
+        // Which package each class was found in, to catch classes measured twice
+        let mut packages = std::collections::HashMap::new();
+
+        for (package, archive) in self.packages.iter().zip(archives) {
+            let classes = package_sources(package, &manifest_dir.join(&package.path))
+                .into_iter()
+                .flat_map(|(module, file)| {
+                    let source = fs::read_to_string(file).unwrap_or_default();
+                    bindings::declared_classes(&source)
+                        .into_iter()
+                        .map(move |class| format!("{module}.{class}"))
+                })
+                .collect::<Vec<_>>();
+            if classes.is_empty() {
+                continue;
             }
 
-            command
-                // Build the package (duh)
-                .arg("build")
-                // SDK path for regular compilation (idk)
-                .args(["--sdk", sdk_path.trim()])
-                // Release/Debug configuration
-                .args(["-c", configuration]);
-
-            if use_triple {
-                command.args(["--triple", &swift_target_triple]);
-            } else {
-                command.args(["--arch", arch]);
+            for qualified in &classes {
+                if let Some(other) = packages.insert(qualified.clone(), &package.name) {
+                    return Err(SwiftLinkError::LayoutProbe(format!(
+                        "class `{qualified}` is declared in both package `{other}` and package `{}`",
+                        package.name
+                    )));
+                }
             }
 
-            // Where the artifacts will be generated to
-            command.args(["--build-path", &out_path.display().to_string()]);
+            let layouts = if can_run {
+                let target = rust_target
+                    .sdk
+                    .as_ref()
+                    .map(|_| swift_env.target.triple.as_str());
+                Some(
+                    layout::probe(&out_dir.join(&package.name), archive, target, &classes)
+                        .map_err(SwiftLinkError::LayoutProbe)?,
+                )
+            } else {
+                None
+            };
 
-            if !use_triple {
-                // Override the SDK and target on each swiftc instance.
-                command
-                    .args(["-Xswiftc", "-sdk"])
-                    .args(["-Xswiftc", sdk_path.trim()])
-                    .args(["-Xswiftc", "-target"])
-                    .args(["-Xswiftc", &swift_target_triple]);
+            for qualified in &classes {
+                match &layouts {
+                    Some(layouts) => {
+                        // Classes the probe couldn't find, such as nested or generic classes,
+                        // are left out so that deriving for them fails
+                        if let Some(layout) = layouts.get(qualified) {
+                            layout::write_layout(&layouts_dir, qualified, Some(layout))
+                                .map_err(io_error)?;
+                        }
+                    }
+                    None => {
+                        layout::write_layout(&layouts_dir, qualified, None).map_err(io_error)?
+                    }
+                }
             }
+        }
 
+        Ok(warnings)
+    }
+
+    /// Generates bindings for every package and writes them to `file_name` in `OUT_DIR`,
+    /// returning warnings for any functions that were skipped.
+    fn write_bindings(&self, file_name: &Path) -> Result<Vec<String>, SwiftLinkError> {
+        let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+
+        let sources = self
+            .packages
+            .iter()
+            .flat_map(|package| package_sources(package, &manifest_dir.join(&package.path)))
+            .filter_map(|(_, file)| fs::read_to_string(file).ok())
+            .collect::<Vec<_>>();
+
+        let (module, warnings) = bindings::generate(&sources);
+
+        let path = Path::new(&env::var("OUT_DIR").unwrap()).join(file_name);
+        fs::write(&path, module)
+            .map_err(|source| SwiftLinkError::WriteBindings { path, source })?;
+
+        Ok(warnings)
+    }
+
+    /// Builds a package with the Xcode toolchain,
+    /// returning the directory containing its static archive.
+    fn build_apple_package(
+        &self,
+        package: &SwiftPackage,
+        rust_target: &RustTarget,
+        sdk: &SwiftSDK,
+        out_path: &Path,
+        configuration: &str,
+    ) -> Result<PathBuf, SwiftLinkError> {
+        let sdk_path_output = Command::new("xcrun")
+            .args(["--sdk", &sdk.to_string(), "--show-sdk-path"])
+            .output()
+            .map_err(|source| SwiftLinkError::MissingToolchain {
+                tool: "xcrun".to_string(),
+                source,
+            })?;
+        if !sdk_path_output.status.success() {
+            return Err(SwiftLinkError::SdkLookup(format!(
+                "Failed to get SDK path with `xcrun --sdk {} --show-sdk-path`",
+                sdk
+            )));
+        }
+
+        let sdk_path = String::from_utf8_lossy(&sdk_path_output.stdout);
+
+        let mut command = Command::new("swift");
+        command.current_dir(&package.path);
+
+        let arch = match std::env::consts::ARCH {
+            "aarch64" => "arm64",
+            arch => arch,
+        };
+
+        let mut swift_target_triple = rust_target.swift_target_triple(
+            &self.macos_min_version,
+            self.ios_min_version.as_deref(),
+            self.visionos_min_version.as_deref(),
+        );
+
+        // Xcode 27's SwiftPM appends the host -sdk/-target after the -Xswiftc
+        // overrides below, so cross builds compile against the host SDK. Pass
+        // --triple there instead. macOS (host == target) is unaffected and
+        // keeps the legacy path.
+        let xcode27 = xcode_major_version().map(|v| v >= 27).unwrap_or(false);
+        let cross_compiling = !matches!(rust_target.os, RustTargetOS::MacOS);
+        let use_triple = cross_compiling && xcode27;
+
+        // Xcode 27 SDKs reject iOS deployment targets below 15
+        // ("supported deployment target versions is 15.0 to 27.0.x") and
+        // consumers commonly pass lower minimums (tauri passes ios13.0).
+        if xcode27 {
+            clamp_ios_deployment_target(&mut swift_target_triple, 15);
+        }
+
+        command
+            // Build the package (duh)
+            .arg("build")
+            // SDK path for regular compilation (idk)
+            .args(["--sdk", sdk_path.trim()])
+            // Release/Debug configuration
+            .args(["-c", configuration]);
+
+        if use_triple {
+            command.args(["--triple", &swift_target_triple]);
+        } else {
+            command.args(["--arch", arch]);
+        }
+
+        // Where the artifacts will be generated to
+        command.args(["--build-path", &out_path.display().to_string()]);
+
+        if !use_triple {
+            // Override the SDK and target on each swiftc instance.
             command
-                .args(["-Xcc", &format!("--target={swift_target_triple}")])
-                .args(["-Xcxx", &format!("--target={swift_target_triple}")]);
+                .args(["-Xswiftc", "-sdk"])
+                .args(["-Xswiftc", sdk_path.trim()])
+                .args(["-Xswiftc", "-target"])
+                .args(["-Xswiftc", &swift_target_triple]);
+        }
 
-            println!("Command `{command:?}`");
+        command
+            .args(["-Xcc", &format!("--target={swift_target_triple}")])
+            .args(["-Xcxx", &format!("--target={swift_target_triple}")]);
 
-            if !command.status().unwrap().success() {
-                panic!("Failed to compile swift package {}", package.name);
-            }
+        run_swift_build(command, package)?;
 
-            let search_path = if xcode27 {
-                // Xcode 27 SwiftPM layouts vary by beta. Trust no path unless it
-                // actually CONTAINS the archive: the legacy `<configuration>` dir
-                // can exist yet be empty, while the real products live under
-                // [out/]Products/<Configuration>-<platform>
-                // (e.g. out/Products/Release-iphoneos on 27A5218g).
-                let lib_file = format!("lib{}.a", package.name);
-                let direct = out_path.join(configuration);
-                if direct.join(&lib_file).exists() {
-                    direct
-                } else {
-                    xcode27_products_dir(&out_path, configuration, &lib_file).unwrap_or(direct)
-                }
+        let search_path = if xcode27 {
+            // Xcode 27 SwiftPM layouts vary by beta. Trust no path unless it
+            // actually CONTAINS the archive: the legacy `<configuration>` dir
+            // can exist yet be empty, while the real products live under
+            // [out/]Products/<Configuration>-<platform>
+            // (e.g. out/Products/Release-iphoneos on 27A5218g).
+            let lib_file = format!("lib{}.a", package.name);
+            let direct = out_path.join(configuration);
+            if direct.join(&lib_file).exists() {
+                direct
             } else {
-                out_path
-                    .join(format!("{}-apple-macosx", arch))
-                    .join(configuration)
-            };
+                xcode27_products_dir(out_path, configuration, &lib_file).unwrap_or(direct)
+            }
+        } else {
+            out_path
+                .join(format!("{}-apple-macosx", arch))
+                .join(configuration)
+        };
 
-            if xcode27 {
-                // Xcode 27's SwiftPM internalizes @_cdecl exports in static
-                // products (they show as local 't' in nm), so consumers fail to
-                // link with "undefined symbols". Promote them back to global.
-                globalize_cdecl_symbols(
-                    &search_path.join(format!("lib{}.a", package.name)),
-                    &package.name,
-                );
+        if xcode27 {
+            // Xcode 27's SwiftPM internalizes @_cdecl exports in static
+            // products (they show as local 't' in nm), so consumers fail to
+            // link with "undefined symbols". Promote them back to global.
+            globalize_cdecl_symbols(
+                &search_path.join(format!("lib{}.a", package.name)),
+                &package.name,
+            );
+        }
+
+        Ok(search_path)
+    }
+}
+
+/// Builds a package with a swift.org toolchain,
+/// returning the directory containing its static archive.
+fn build_linux_package(
+    package: &SwiftPackage,
+    rust_target: &RustTarget,
+    out_path: &Path,
+    configuration: &str,
+) -> Result<PathBuf, SwiftLinkError> {
+    let triple = rust_target.unversioned_swift_target_triple();
+
+    let mut command = Command::new("swift");
+    command
+        .current_dir(&package.path)
+        .arg("build")
+        .args(["-c", configuration])
+        .args(["--build-path", &out_path.display().to_string()]);
+
+    // Only cross compiles need an explicit triple,
+    // and they also require a matching Swift SDK to be installed.
+    if rust_target.arch != std::env::consts::ARCH {
+        command.args(["--triple", &triple]);
+    }
+
+    run_swift_build(command, package)?;
+
+    Ok(out_path.join(triple).join(configuration))
+}
+
+/// Runs a `swift build` command, capturing its output in case it fails.
+fn run_swift_build(mut command: Command, package: &SwiftPackage) -> Result<(), SwiftLinkError> {
+    println!("Command `{command:?}`");
+
+    let output = command
+        .output()
+        .map_err(|source| SwiftLinkError::MissingToolchain {
+            tool: "swift".to_string(),
+            source,
+        })?;
+
+    // Forwarded to stderr so it shows up with `cargo -vv`,
+    // without Cargo trying to interpret any of it as instructions
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    eprint!("{stdout}{stderr}");
+
+    if !output.status.success() {
+        return Err(SwiftLinkError::PackageCompile {
+            package: package.name.clone(),
+            stdout,
+            stderr,
+        });
+    }
+
+    Ok(())
+}
+
+/// Files that a package and its local path dependencies are built from:
+/// manifests, `Package.resolved`, and the contents of each target's directory.
+/// Target directories are included too so that new files are noticed.
+///
+/// Packages in `visited` are skipped, so shared dependencies are only listed once.
+fn package_inputs(package_path: &Path, visited: &mut HashSet<PathBuf>) -> Vec<PathBuf> {
+    package_inputs_inner(package_path, visited, &dump_package)
+}
+
+fn package_inputs_inner(
+    package_path: &Path,
+    visited: &mut HashSet<PathBuf>,
+    dump_package: &dyn Fn(&Path) -> Option<serde_json::Value>,
+) -> Vec<PathBuf> {
+    let package_path = package_path
+        .canonicalize()
+        .unwrap_or_else(|_| package_path.to_path_buf());
+    if !visited.insert(package_path.clone()) {
+        return vec![];
+    }
+
+    // Without the manifest, fall back to tracking the whole package directory
+    let Some(manifest) = dump_package(&package_path) else {
+        return vec![package_path];
+    };
+
+    // Package.resolved is tracked even if it doesn't exist yet,
+    // since Cargo reruns the build script once a missing file is created
+    let mut inputs = vec![package_path.join("Package.resolved")];
+
+    // Package.swift, plus any version-specific Package@swift-x.y.swift manifests
+    if let Ok(entries) = fs::read_dir(&package_path) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name.starts_with("Package") && name.ends_with(".swift") {
+                inputs.push(entry.path());
             }
+        }
+    }
 
-            println!("cargo:rerun-if-changed={}", package_path.display());
-            println!("cargo:rustc-link-search=native={}", search_path.display());
-            println!("cargo:rustc-link-lib=static={}", package.name);
+    for target in manifest_targets(&package_path, &manifest) {
+        inputs.push(target.path.clone());
+        collect_files(&target.path, &mut inputs);
+    }
+
+    for dependency in manifest["dependencies"].as_array().into_iter().flatten() {
+        // Swift 5.6+ manifests list local packages under `fileSystem`,
+        // older ones as a `url` with a `localPackage` requirement.
+        let path = dependency["fileSystem"][0]["path"].as_str().or_else(|| {
+            dependency["requirement"]
+                .get("localPackage")
+                .and(dependency["url"].as_str())
+        });
+
+        if let Some(path) = path {
+            inputs.extend(package_inputs_inner(
+                &package_path.join(path),
+                visited,
+                dump_package,
+            ));
+        }
+    }
+
+    inputs
+}
+
+/// Swift source files in a package's library targets, excluding those of its dependencies,
+/// along with the name of the module each one is compiled into.
+fn package_sources(package: &SwiftPackage, package_path: &Path) -> Vec<(String, PathBuf)> {
+    let mut sources = vec![];
+
+    match dump_package(package_path) {
+        Some(manifest) => {
+            for target in manifest_targets(package_path, &manifest) {
+                if matches!(target.kind.as_deref(), None | Some("regular")) {
+                    let mut files = vec![];
+                    collect_files(&target.path, &mut files);
+                    let module = module_name(&target.name);
+                    sources.extend(files.into_iter().map(|file| (module.clone(), file)));
+                }
+            }
+        }
+        None => {
+            let mut files = vec![];
+            collect_files(package_path, &mut files);
+            let module = module_name(&package.name);
+            sources.extend(
+                files
+                    .into_iter()
+                    .filter(|file| {
+                        !file
+                            .file_name()
+                            .is_some_and(|name| name.to_string_lossy().starts_with("Package"))
+                    })
+                    .map(|file| (module.clone(), file)),
+            );
+        }
+    }
+
+    sources.retain(|(_, file)| file.extension().is_some_and(|ext| ext == "swift"));
+    sources
+}
+
+/// SwiftPM's module name for a target, which replaces characters like `-` with `_`.
+fn module_name(target_name: &str) -> String {
+    target_name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// The JSON form of a package's manifest, from `swift package dump-package`.
+fn dump_package(package_path: &Path) -> Option<serde_json::Value> {
+    Command::new("swift")
+        .current_dir(package_path)
+        .args(["package", "dump-package"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| serde_json::from_slice(&output.stdout).ok())
+}
+
+struct ManifestTarget {
+    name: String,
+    kind: Option<String>,
+    path: PathBuf,
+}
+
+/// The targets in a package's manifest,
+/// skipping binary and system library targets which have no sources.
+fn manifest_targets(package_path: &Path, manifest: &serde_json::Value) -> Vec<ManifestTarget> {
+    let mut targets = vec![];
+
+    for target in manifest["targets"].as_array().into_iter().flatten() {
+        let kind = target["type"].as_str();
+        let default_dir = match kind {
+            Some("test") => "Tests",
+            Some("plugin") => "Plugins",
+            Some("binary") | Some("system") => continue,
+            _ => "Sources",
+        };
+
+        let name = target["name"].as_str().unwrap_or_default();
+        let path = match target["path"].as_str() {
+            Some(path) => package_path.join(path),
+            None => package_path.join(default_dir).join(name),
+        };
+
+        targets.push(ManifestTarget {
+            name: name.to_string(),
+            kind: kind.map(String::from),
+            path,
+        });
+    }
+
+    targets
+}
+
+/// Recursively collects the files in `dir`, skipping hidden entries such as `.build`.
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    collect_files_inner(dir, files, &mut HashSet::new())
+}
+
+fn collect_files_inner(dir: &Path, files: &mut Vec<PathBuf>, visited: &mut HashSet<PathBuf>) {
+    // Symlinked directories are followed like SwiftPM does,
+    // but each directory is only visited once so that symlink loops terminate
+    let Ok(canonical) = dir.canonicalize() else {
+        return;
+    };
+    if !visited.insert(canonical) {
+        return;
+    }
+
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files_inner(&path, files, visited);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+/// Links the shared Swift runtime libraries from a swift.org toolchain.
+/// Unlike on Apple platforms these aren't part of the OS,
+/// so binaries also get an rpath pointing at the toolchain.
+///
+/// The libraries come from the autolink entries that swiftc records in each object,
+/// which is what `swiftc` itself links against.
+/// If those can't be read, a fixed set that covers SwiftRs is linked instead.
+fn link_linux_runtime(swift_env: &SwiftEnv, archives: &[PathBuf], debug: bool) {
+    if swift_env.target.libraries_require_rpath {
+        for path in &swift_env.paths.runtime_library_paths {
+            println!("cargo:rustc-link-arg=-Wl,-rpath,{path}");
         }
     }
+
+    let libraries = autolink_libraries(archives).unwrap_or_else(|| {
+        let mut libraries = [
+            "swiftCore",
+            "Foundation",
+            "swift_Concurrency",
+            "dispatch",
+            "BlocksRuntime",
+        ]
+        .map(String::from)
+        .to_vec();
+        // Debug builds of Swift code reference symbols from the -Onone support library
+        if debug {
+            libraries.push("swiftSwiftOnoneSupport".to_string());
+        }
+        libraries
+    });
+
+    for library in libraries {
+        println!("cargo:rustc-link-lib=dylib={library}");
+    }
 }
 
-fn link_clang_rt(rust_target: &RustTarget) {
-    println!(
-        "cargo:rustc-link-lib=clang_rt.{}",
-        rust_target.sdk.clang_lib_extension()
-    );
-    println!("cargo:rustc-link-search={}", clang_link_search_path());
+/// Libraries listed in the autolink entries of `archives`, from `swift-autolink-extract`.
+fn autolink_libraries(archives: &[PathBuf]) -> Option<Vec<String>> {
+    let output = Command::new("swift-autolink-extract")
+        .args(archives)
+        .args(["-o", "-"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+
+    Some(parse_autolink_entries(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+/// Library names from `swift-autolink-extract`'s output, which has a linker flag per line.
+/// Duplicates are removed, keeping the first occurrence so the order is preserved.
+fn parse_autolink_entries(output: &str) -> Vec<String> {
+    let mut libraries: Vec<String> = vec![];
+
+    for library in output
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("-l"))
+    {
+        if !libraries.iter().any(|existing| existing == library) {
+            libraries.push(library.to_string());
+        }
+    }
+
+    libraries
 }
 
-fn clang_link_search_path() -> String {
-    let output = std::process::Command::new(
-        std::env::var("SWIFT_RS_CLANG").unwrap_or_else(|_| "/usr/bin/clang".to_string()),
-    )
-    .arg("--print-search-dirs")
-    .output()
-    .unwrap();
+fn clang_link_search_path() -> Result<String, SwiftLinkError> {
+    let clang = std::env::var("SWIFT_RS_CLANG").unwrap_or_else(|_| "/usr/bin/clang".to_string());
+    let output = std::process::Command::new(&clang)
+        .arg("--print-search-dirs")
+        .output()
+        .map_err(|source| SwiftLinkError::MissingToolchain {
+            tool: clang,
+            source,
+        })?;
     if !output.status.success() {
-        panic!("Can't get search paths from clang");
+        return Err(SwiftLinkError::SdkLookup(
+            "Can't get search paths from clang".to_string(),
+        ));
     }
     let stdout = String::from_utf8_lossy(&output.stdout);
     for line in stdout.lines() {
         if line.contains("libraries: =") {
             let path = line.split('=').nth(1).unwrap();
-            return format!("{}/lib/darwin", path);
+            return Ok(format!("{}/lib/darwin", path));
         }
     }
-    panic!("clang is missing search paths");
+    Err(SwiftLinkError::SdkLookup(
+        "clang is missing search paths".to_string(),
+    ))
 }
 
 /// Major version of the active Xcode (e.g. `27`), or `None` if undetectable.
@@ -486,6 +1235,7 @@ fn xcode27_products_dir(
 /// - only plain C names not starting with '_' (compiler helpers like
 ///   ___swift_closure_destructor repeat)
 /// - only names unique within the archive
+///
 /// Violating any of these crashes Xcode 27's ld with "malformed atom files with
 /// duplicate names" (AtomSymbolTable.cpp:242) — and -ld_classic is removed.
 fn globalize_cdecl_symbols(archive: &std::path::Path, package_name: &str) {
@@ -572,3 +1322,196 @@ fn rustup_llvm_objcopy() -> Option<std::path::PathBuf> {
         .join("bin/llvm-objcopy");
     p.exists().then_some(p)
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collect_files_terminates_on_symlink_loops() {
+        let dir = env::temp_dir().join(format!("swift-rs-collect-files-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("Sources/Lib")).unwrap();
+        fs::write(dir.join("Sources/Lib/lib.swift"), "").unwrap();
+        std::os::unix::fs::symlink(&dir, dir.join("Sources/Lib/loop")).unwrap();
+
+        let mut files = vec![];
+        collect_files(&dir, &mut files);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(files, [dir.join("Sources/Lib/lib.swift")]);
+    }
+
+    #[test]
+    fn package_inputs_follows_local_dependencies() {
+        let dir = env::temp_dir().join(format!("swift-rs-package-inputs-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        for file in [
+            "App/Package.swift",
+            "App/Sources/App/main.swift",
+            "Dep/Package.swift",
+            "Dep/Package.resolved",
+            "Dep/Sources/DepLib/dep.swift",
+            "Shared/Package.swift",
+            "Shared/Sources/Shared/shared.swift",
+        ] {
+            let file = dir.join(file);
+            fs::create_dir_all(file.parent().unwrap()).unwrap();
+            fs::write(file, "").unwrap();
+        }
+        let dir = dir.canonicalize().unwrap();
+
+        // App depends on Dep and Shared, and Dep depends on Shared too.
+        // Shared has no manifest, like a package that `swift package` fails to load.
+        let dump_package = |path: &Path| -> Option<serde_json::Value> {
+            match path.file_name()?.to_str()? {
+                "App" => Some(serde_json::json!({
+                    "targets": [{ "name": "App", "type": "executable" }],
+                    "dependencies": [
+                        { "fileSystem": [{ "path": "../Dep" }] },
+                        { "fileSystem": [{ "path": "../Shared" }] },
+                    ],
+                })),
+                // Manifests from before Swift 5.6 list local packages by url
+                "Dep" => Some(serde_json::json!({
+                    "targets": [
+                        { "name": "DepLib", "type": "regular" },
+                        { "name": "Prebuilt", "type": "binary" },
+                    ],
+                    "dependencies": [
+                        { "url": "../Shared", "requirement": { "localPackage": null } },
+                    ],
+                })),
+                _ => None,
+            }
+        };
+
+        let mut visited = HashSet::new();
+        let mut inputs = package_inputs_inner(&dir.join("App"), &mut visited, &dump_package);
+        // Already visited, so nothing is listed twice
+        assert!(package_inputs_inner(&dir.join("Dep"), &mut visited, &dump_package).is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+
+        inputs.sort();
+        let mut expected = [
+            // Tracked even though it doesn't exist yet
+            "App/Package.resolved",
+            "App/Package.swift",
+            "App/Sources/App",
+            "App/Sources/App/main.swift",
+            "Dep/Package.resolved",
+            "Dep/Package.swift",
+            "Dep/Sources/DepLib",
+            "Dep/Sources/DepLib/dep.swift",
+            "Shared",
+        ]
+        .map(|path| dir.join(path));
+        expected.sort();
+        assert_eq!(inputs, expected);
+    }
+
+    #[cfg(test_swift_rs)]
+    #[test]
+    fn package_inputs_reads_manifests() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .canonicalize()
+            .unwrap();
+        let inputs = package_inputs(&root.join("tests/swift-pkg"), &mut HashSet::new());
+
+        for input in [
+            "tests/swift-pkg/Package.swift",
+            "tests/swift-pkg/lib.swift",
+            // From the SwiftRs package, which is a local path dependency
+            "Package.swift",
+            "src-swift/lib.swift",
+        ] {
+            assert!(inputs.contains(&root.join(input)), "missing {input}");
+        }
+    }
+
+    #[test]
+    fn parses_autolink_entries() {
+        let output = "-lswiftCore\n-lFoundation\n-lswift_Concurrency\n-lswiftCore\n\
+                      -ldispatch\n-lBlocksRuntime\n-fuse-ld=gold\n";
+
+        assert_eq!(
+            parse_autolink_entries(output),
+            [
+                "swiftCore",
+                "Foundation",
+                "swift_Concurrency",
+                "dispatch",
+                "BlocksRuntime"
+            ]
+        );
+    }
+
+    fn test_package(name: &str) -> SwiftPackage {
+        SwiftPackage {
+            name: name.to_string(),
+            path: PathBuf::from("."),
+        }
+    }
+
+    #[test]
+    fn package_compile_error_includes_stdout_and_stderr() {
+        // SwiftPM prints compiler diagnostics to stdout and its own errors to stderr
+        let mut command = Command::new("sh");
+        command.args([
+            "-c",
+            "echo \"lib.swift:1:1: error: cannot find 'foo' in scope\"; \
+             echo 'error: fatalError' >&2; exit 1",
+        ]);
+
+        let error = run_swift_build(command, &test_package("broken")).unwrap_err();
+        let SwiftLinkError::PackageCompile {
+            package,
+            stdout,
+            stderr,
+        } = &error
+        else {
+            panic!("unexpected error: {error}");
+        };
+        assert_eq!(package, "broken");
+        assert!(stdout.contains("cannot find 'foo' in scope"));
+        assert_eq!(stderr.trim(), "error: fatalError");
+
+        let message = error.to_string();
+        assert!(message.contains("cannot find 'foo' in scope"));
+        assert!(message.contains("error: fatalError"));
+    }
+
+    #[cfg(test_swift_rs)]
+    #[test]
+    fn failing_package_reports_compiler_errors() {
+        let dir = env::temp_dir().join(format!("swift-rs-broken-package-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("Sources/Broken")).unwrap();
+        fs::write(
+            dir.join("Package.swift"),
+            "// swift-tools-version:5.3\n\
+             import PackageDescription\n\
+             let package = Package(name: \"Broken\", targets: [.target(name: \"Broken\")])\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("Sources/Broken/lib.swift"),
+            "func broken() -> Int { return undefinedValue }\n",
+        )
+        .unwrap();
+
+        let mut command = Command::new("swift");
+        command
+            .current_dir(&dir)
+            .arg("build")
+            .args(["--build-path", &dir.join(".build").display().to_string()]);
+        let error = run_swift_build(command, &test_package("Broken")).unwrap_err();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(matches!(error, SwiftLinkError::PackageCompile { .. }));
+        assert!(
+            error.to_string().contains("undefinedValue"),
+            "compiler message missing from: {error}"
+        );
+    }
+}
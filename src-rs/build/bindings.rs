@@ -0,0 +1,811 @@
+//! Generates `swift!` declarations from the `@_cdecl` functions in Swift sources.
+//!
+//! This isn't a full Swift parser, it only understands enough of a function's signature
+//! to map its parameter and return types onto the types `swift!` accepts.
+//!
+//! Objects are usually passed to `@_cdecl` functions as untyped pointers,
+//! so those need an annotation naming the type they point to,
+//! such as `array: UnsafeRawPointer /* SRArray<Int> */`.
+//!
+//! Functions using `SRClosure`, `SREnum`, `SRResult`, `SRTask`, `SRWeak`
+//! or `SRMutableBuffer` aren't mapped, since their Rust types depend on
+//! things the Swift signature doesn't say, such as a closure's arguments or a result's value.
+
+use std::collections::HashSet;
+
+const SCALARS: &[(&str, &str)] = &[
+    ("Bool", "Bool"),
+    ("Int", "Int"),
+    ("Int8", "Int8"),
+    ("Int16", "Int16"),
+    ("Int32", "Int32"),
+    ("Int64", "Int64"),
+    ("UInt", "UInt"),
+    ("UInt8", "UInt8"),
+    ("UInt16", "UInt16"),
+    ("UInt32", "UInt32"),
+    ("UInt64", "UInt64"),
+    ("Float", "Float32"),
+    ("Float32", "Float32"),
+    ("Double", "Float64"),
+    ("Float64", "Float64"),
+];
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else", "enum",
+    "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+    "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true",
+    "try", "type", "unsafe", "use", "where", "while", "yield",
+];
+
+/// A parameter's internal name and type.
+type Parameter = (String, String);
+
+/// Generates a module of `swift!` declarations for the `@_cdecl` functions in `sources`.
+///
+/// Classes declared in `sources` are assumed to have a `#[repr(C)]` Rust struct
+/// of the same name in scope where the module is included.
+///
+/// Returns the module's contents, and a warning for each function that was skipped.
+pub(super) fn generate(sources: &[String]) -> (String, Vec<String>) {
+    let sources = sources
+        .iter()
+        .map(|source| strip_comments(&apply_annotations(source)))
+        .collect::<Vec<_>>();
+
+    let types = TypeMapper {
+        classes: sources.iter().flat_map(|s| class_names(s)).collect(),
+    };
+
+    let mut module = String::from(
+        "// Generated by swift-rs from the `@_cdecl` functions in your Swift packages.\n\
+         // Any changes will be overwritten.\n",
+    );
+    let mut warnings = vec![];
+    let mut symbols = HashSet::new();
+
+    for (symbol, signature) in sources.iter().flat_map(|s| cdecl_functions(s)) {
+        if !symbols.insert(symbol.clone()) {
+            warnings.push(format!("Skipping duplicate `@_cdecl` function `{symbol}`"));
+            continue;
+        }
+
+        match declaration(&symbol, &signature, &types) {
+            Ok(declaration) => {
+                module.push('\n');
+                module.push_str(&declaration);
+                module.push('\n');
+            }
+            Err(reason) => {
+                warnings.push(format!("Skipping `@_cdecl` function `{symbol}`: {reason}"))
+            }
+        }
+    }
+
+    (module, warnings)
+}
+
+/// Names of the classes declared in a Swift source file.
+pub(super) fn declared_classes(source: &str) -> Vec<String> {
+    class_names(&strip_comments(source))
+}
+
+/// Builds the `swift!` declaration for a single function.
+fn declaration(symbol: &str, signature: &str, types: &TypeMapper) -> Result<String, String> {
+    if !is_identifier(symbol) || RUST_KEYWORDS.contains(&symbol) {
+        return Err("its name isn't a valid Rust identifier".to_string());
+    }
+
+    let (params, ret) = parse_signature(signature)?;
+
+    let args = params
+        .iter()
+        .enumerate()
+        .map(|(i, (name, ty))| {
+            let name = match name.as_str() {
+                "_" => format!("arg{i}"),
+                name => rust_argument_name(name),
+            };
+            Ok(format!("{name}: {}", types.arg(ty)?))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let ret = match ret {
+        Some(ret) => types
+            .ret(&ret)?
+            .map(|ret| format!(" -> {ret}"))
+            .unwrap_or_default(),
+        None => String::new(),
+    };
+
+    Ok(format!(
+        "swift_rs::swift!(pub fn {symbol}({}){ret});",
+        args.join(", ")
+    ))
+}
+
+/// Maps Swift types onto the types accepted by `swift!`.
+struct TypeMapper {
+    classes: HashSet<String>,
+}
+
+impl TypeMapper {
+    fn arg(&self, ty: &str) -> Result<String, String> {
+        if let Some(pointer) = pointer_type(ty)? {
+            return Ok(pointer);
+        }
+
+        match ty.strip_suffix('?') {
+            Some(inner) if self.object(inner).is_some() => {
+                Ok(format!("Option<&{}>", self.object(inner).unwrap()))
+            }
+            Some(_) => Err(format!("optional argument type `{ty}` isn't supported")),
+            None => scalar(ty)
+                .map(|scalar| format!("swift_rs::{scalar}"))
+                .or_else(|| optional_scalar(ty))
+                .or_else(|| self.object(ty).map(|object| format!("&{object}")))
+                .ok_or_else(|| format!("argument type `{ty}` isn't supported")),
+        }
+    }
+
+    /// `None` if the function doesn't return anything.
+    fn ret(&self, ty: &str) -> Result<Option<String>, String> {
+        if ty == "Void" || ty == "()" {
+            return Ok(None);
+        }
+
+        if let Some(pointer) = pointer_type(ty)? {
+            return Ok(Some(pointer));
+        }
+
+        match ty.strip_suffix('?') {
+            Some(inner) => self
+                .object(inner)
+                .map(|object| Some(format!("Option<{object}>")))
+                .ok_or_else(|| format!("optional return type `{ty}` isn't supported")),
+            None => scalar(ty)
+                .map(|scalar| format!("swift_rs::{scalar}"))
+                .or_else(|| optional_scalar(ty))
+                .or_else(|| self.object(ty))
+                .map(Some)
+                .ok_or_else(|| format!("return type `{ty}` isn't supported")),
+        }
+    }
+
+    fn object(&self, ty: &str) -> Option<String> {
+        match ty {
+            "SRString" | "SRData" => Some(format!("swift_rs::{ty}")),
+            // The element type isn't known from the Swift side
+            "SRObjectArray" => Some("swift_rs::SRObjectArray<()>".to_string()),
+            _ => {
+                if let Some((collection, elements)) = ty
+                    .split_once('<')
+                    .filter(|(collection, _)| {
+                        matches!(*collection, "SRArray" | "SRSet" | "SRDictionary")
+                    })
+                    .and_then(|(collection, rest)| Some((collection, rest.strip_suffix('>')?)))
+                {
+                    let elements = split_top_level(elements)
+                        .iter()
+                        .map(|element| self.element(element))
+                        .collect::<Option<Vec<_>>>()?;
+                    let expected = if collection == "SRDictionary" { 2 } else { 1 };
+                    (elements.len() == expected)
+                        .then(|| format!("swift_rs::{collection}<{}>", elements.join(", ")))
+                } else if self.classes.contains(ty) {
+                    Some(format!("swift_rs::SRObject<{ty}>"))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// An element of an `SRArray`, `SRSet` or `SRDictionary`.
+    fn element(&self, ty: &str) -> Option<String> {
+        match scalar(ty) {
+            Some(scalar) => Some(format!("swift_rs::{scalar}")),
+            None => self.object(ty),
+        }
+    }
+}
+
+/// Pointers to scalars, which are passed through as-is.
+///
+/// Untyped pointers are rejected, since they're almost always objects
+/// that should have been annotated with their type.
+fn pointer_type(ty: &str) -> Result<Option<String>, String> {
+    let ty = ty.strip_suffix('?').unwrap_or(ty);
+
+    if matches!(
+        ty,
+        "UnsafeRawPointer" | "UnsafeMutableRawPointer" | "OpaquePointer"
+    ) {
+        return Err(format!(
+            "untyped pointer `{ty}` needs an annotation naming its type, such as `{ty} /* SRString */`"
+        ));
+    }
+
+    let (kind, pointee) = if let Some(pointee) = ty.strip_prefix("UnsafePointer<") {
+        ("*const", pointee)
+    } else if let Some(pointee) = ty.strip_prefix("UnsafeMutablePointer<") {
+        ("*mut", pointee)
+    } else {
+        return Ok(None);
+    };
+
+    Ok(pointee
+        .strip_suffix('>')
+        .and_then(scalar)
+        .map(|rust| format!("{kind} swift_rs::{rust}")))
+}
+
+fn scalar(ty: &str) -> Option<&'static str> {
+    SCALARS
+        .iter()
+        .find(|(swift, _)| *swift == ty)
+        .map(|(_, rust)| *rust)
+}
+
+/// The C structs that `SwiftRs` declares for optional scalars, such as `SROptionalInt`.
+fn optional_scalar(ty: &str) -> Option<String> {
+    let scalar = scalar(ty.strip_prefix("SROptional")?)?;
+    Some(format!("Option<swift_rs::{scalar}>"))
+}
+
+/// Splits a signature starting at `func` into its parameters' names and types,
+/// and the return type if there is one.
+fn parse_signature(signature: &str) -> Result<(Vec<Parameter>, Option<String>), String> {
+    let rest = signature
+        .trim_start()
+        .strip_prefix("func")
+        .ok_or("it isn't a function")?;
+    let open = rest.find('(').ok_or("its parameters couldn't be parsed")?;
+    if rest[..open].contains('<') {
+        return Err("generic functions aren't supported".to_string());
+    }
+
+    let close = matching_paren(rest, open).ok_or("its parameters couldn't be parsed")?;
+    let params = split_top_level(&rest[open + 1..close])
+        .into_iter()
+        .filter(|param| !param.is_empty())
+        .map(|param| parse_parameter(&param))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut effects = rest[close + 1..].trim();
+    let ret = match effects.find("->") {
+        Some(arrow) => {
+            let ret = normalize_type(&effects[arrow + 2..]);
+            effects = &effects[..arrow];
+            Some(ret)
+        }
+        None => None,
+    };
+
+    if effects
+        .split_whitespace()
+        .any(|e| e == "throws" || e == "async")
+    {
+        return Err("`async` and `throws` functions aren't supported".to_string());
+    }
+
+    Ok((params, ret))
+}
+
+/// Parses a parameter such as `label name: Type = default` into its internal name and type.
+fn parse_parameter(param: &str) -> Result<Parameter, String> {
+    let (names, ty) = param
+        .split_once(':')
+        .ok_or_else(|| format!("parameter `{param}` couldn't be parsed"))?;
+    let ty = ty.split('=').next().unwrap_or_default();
+
+    if ty.contains('@') || ty.split_whitespace().any(|word| word == "inout") {
+        return Err(format!("parameter `{}` isn't supported", param.trim()));
+    }
+
+    let name = names.split_whitespace().last().unwrap_or("_").to_string();
+    Ok((name, normalize_type(ty)))
+}
+
+/// Removes whitespace and spells optionals as `T?`.
+fn normalize_type(ty: &str) -> String {
+    let ty: String = ty.chars().filter(|c| !c.is_whitespace()).collect();
+    let ty = ty.strip_prefix("Swift.").unwrap_or(&ty);
+    let ty = ty.strip_prefix("SwiftRs.").unwrap_or(ty);
+
+    if let Some(inner) = ty
+        .strip_prefix("Optional<")
+        .and_then(|ty| ty.strip_suffix('>'))
+    {
+        format!("{}?", normalize_type(inner))
+    } else if let Some(inner) = ty.strip_suffix('!') {
+        format!("{inner}?")
+    } else {
+        ty.to_string()
+    }
+}
+
+/// Finds every `@_cdecl("symbol")` attribute,
+/// returning its symbol and the signature of the function that follows it.
+fn cdecl_functions(source: &str) -> Vec<(String, String)> {
+    let mut functions = vec![];
+    let mut rest = source;
+
+    while let Some(start) = rest.find("@_cdecl") {
+        rest = &rest[start + "@_cdecl".len()..];
+
+        let Some(symbol) = rest
+            .trim_start()
+            .strip_prefix('(')
+            .and_then(|rest| rest.trim_start().strip_prefix('"'))
+            .and_then(|rest| rest.split('"').next())
+        else {
+            continue;
+        };
+
+        let Some(func) = find_keyword(rest, "func") else {
+            continue;
+        };
+        let signature = &rest[func..];
+
+        // The signature ends at the body, which can't start until the parameters are closed
+        let end = signature
+            .find('(')
+            .and_then(|open| matching_paren(signature, open))
+            .and_then(|close| signature[close..].find('{').map(|body| close + body))
+            .unwrap_or(signature.len());
+
+        functions.push((symbol.to_string(), signature[..end].to_string()));
+    }
+
+    functions
+}
+
+/// Names of the classes declared in `source`.
+fn class_names(source: &str) -> Vec<String> {
+    let mut names = vec![];
+    let mut rest = source;
+
+    while let Some(start) = find_keyword(rest, "class") {
+        rest = &rest[start + "class".len()..];
+
+        let name: String = rest
+            .trim_start()
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .collect();
+
+        // Skips `class func` and `class var` members
+        if !name.is_empty() && !matches!(name.as_str(), "func" | "var" | "let") {
+            names.push(name);
+        }
+    }
+
+    names
+}
+
+/// Replaces untyped pointers followed by a `/* Type */` annotation with the annotated type,
+/// so `UnsafeRawPointer /* SRString? */` is read as `SRString?`.
+fn apply_annotations(source: &str) -> String {
+    const POINTERS: [&str; 3] = [
+        "UnsafeRawPointer",
+        "UnsafeMutableRawPointer",
+        "OpaquePointer",
+    ];
+
+    let mut output = String::with_capacity(source.len());
+    let mut rest = source;
+
+    while let Some((start, pointer)) = POINTERS
+        .iter()
+        .filter_map(|pointer| find_keyword(rest, pointer).map(|i| (i, *pointer)))
+        .min()
+    {
+        output.push_str(&rest[..start]);
+
+        let after = &rest[start + pointer.len()..];
+        let annotation = after
+            .strip_prefix('?')
+            .unwrap_or(after)
+            .trim_start()
+            .strip_prefix("/*")
+            .and_then(|comment| comment.split_once("*/"));
+
+        match annotation {
+            Some((ty, remaining)) if !ty.trim().is_empty() => {
+                output.push_str(ty.trim());
+                rest = remaining;
+            }
+            _ => {
+                output.push_str(pointer);
+                rest = after;
+            }
+        }
+    }
+    output.push_str(rest);
+
+    output
+}
+
+/// Replaces comments with whitespace, leaving string literals intact.
+fn strip_comments(source: &str) -> String {
+    let mut output = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                in_string = !in_string;
+                output.push(c);
+            }
+            '\\' if in_string => {
+                output.push(c);
+                output.extend(chars.next());
+            }
+            '/' if !in_string && chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        output.push(c);
+                        break;
+                    }
+                }
+            }
+            '/' if !in_string && chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut depth = 1;
+                while depth > 0 {
+                    match chars.next() {
+                        Some('*') if chars.peek() == Some(&'/') => {
+                            chars.next();
+                            depth -= 1;
+                        }
+                        Some('/') if chars.peek() == Some(&'*') => {
+                            chars.next();
+                            depth += 1;
+                        }
+                        Some(_) => {}
+                        None => break,
+                    }
+                }
+                output.push(' ');
+            }
+            _ => output.push(c),
+        }
+    }
+
+    output
+}
+
+/// Byte offset of the first occurrence of `keyword` as a whole word.
+fn find_keyword(source: &str, keyword: &str) -> Option<usize> {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+
+    source.match_indices(keyword).map(|(i, _)| i).find(|&i| {
+        let before = source[..i].chars().next_back();
+        let after = source[i + keyword.len()..].chars().next();
+        !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+    })
+}
+
+/// Byte offset of the `)` matching the `(` at `open`.
+fn matching_paren(source: &str, open: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (i, c) in source[open..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Splits on commas that aren't nested inside brackets.
+fn split_top_level(source: &str) -> Vec<String> {
+    let mut parts = vec![];
+    let mut current = String::new();
+    let mut depth = 0;
+
+    for c in source.chars() {
+        match c {
+            '(' | '[' | '<' => depth += 1,
+            ')' | ']' | '>' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    parts.push(current.trim().to_string());
+
+    parts
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Converts a Swift parameter name like `shouldThrow` into `should_throw`.
+/// Runs of capitals are treated as a single word, so `userID` becomes `user_id`
+/// and `URLString` becomes `url_string`.
+fn rust_argument_name(name: &str) -> String {
+    let chars = name.trim_matches('`').chars().collect::<Vec<_>>();
+    let mut snake = String::with_capacity(chars.len());
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let previous = i.checked_sub(1).map(|i| chars[i]);
+            let next = chars.get(i + 1);
+            let starts_word = previous.is_some_and(|p| p.is_lowercase() || p.is_numeric())
+                || previous.is_some_and(char::is_uppercase)
+                    && next.is_some_and(|n| n.is_lowercase());
+            if starts_word {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+
+    if RUST_KEYWORDS.contains(&snake.as_str()) {
+        snake.push('_');
+    }
+    snake
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate_one(source: &str) -> (String, Vec<String>) {
+        generate(&[source.to_string()])
+    }
+
+    fn declarations(source: &str) -> Vec<String> {
+        let (module, _) = generate_one(source);
+        module
+            .lines()
+            .filter(|line| line.starts_with("swift_rs::swift!"))
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn maps_scalars_and_names() {
+        let source = r#"
+            @_cdecl("scale")
+            func scale(_ value: Double, by factor: Int32, isEnabled: Bool, _: Int) -> Float {}
+        "#;
+
+        assert_eq!(
+            declarations(source),
+            [
+                "swift_rs::swift!(pub fn scale(value: swift_rs::Float64, factor: swift_rs::Int32, \
+                 is_enabled: swift_rs::Bool, arg3: swift_rs::Int) -> swift_rs::Float32);"
+            ]
+        );
+    }
+
+    #[test]
+    fn converts_acronyms_in_names() {
+        for (swift, rust) in [
+            ("URL", "url"),
+            ("userID", "user_id"),
+            ("URLString", "url_string"),
+            ("parseHTTPResponse", "parse_http_response"),
+            ("base64Data", "base64_data"),
+            ("`type`", "type_"),
+        ] {
+            assert_eq!(rust_argument_name(swift), rust);
+        }
+    }
+
+    #[test]
+    fn maps_collections() {
+        let source = r#"
+            @_cdecl("collections")
+            func collections(
+                names: UnsafeRawPointer /* SRDictionary<Int, SRString> */,
+                ids: UnsafeRawPointer /* SRSet<Int> */
+            ) -> UnsafeMutableRawPointer /* SRArray<SRArray<Double>> */ {}
+
+            @_cdecl("unsupported_result")
+            func unsupportedResult() -> UnsafeMutableRawPointer /* SRResult */ {}
+        "#;
+
+        let (module, warnings) = generate_one(source);
+        assert!(module.contains(
+            "swift_rs::swift!(pub fn collections(names: &swift_rs::SRDictionary<swift_rs::Int, \
+             swift_rs::SRString>, ids: &swift_rs::SRSet<swift_rs::Int>) -> \
+             swift_rs::SRArray<swift_rs::SRArray<swift_rs::Float64>>);"
+        ));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("`SRResult`"));
+    }
+
+    #[test]
+    fn skips_attributes_and_modifiers() {
+        let source = r#"
+            @_cdecl("attributed")
+            @available(macOS 10.15, *)
+            @discardableResult
+            public func attributed(value: Int) -> Int {}
+
+            @_cdecl("escaping")
+            func escaping(callback: @escaping @convention(c) () -> Void) {}
+        "#;
+
+        let (module, warnings) = generate_one(source);
+        assert!(module.contains(
+            "swift_rs::swift!(pub fn attributed(value: swift_rs::Int) -> swift_rs::Int);"
+        ));
+        assert!(!module.contains("escaping"));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("`escaping`"));
+    }
+
+    #[test]
+    fn handles_generics() {
+        let source = r#"
+            @_cdecl("generic")
+            func generic<T>(value: T) {}
+
+            @_cdecl("arrays")
+            func arrays(ints: UnsafeRawPointer /* SRArray<Int> */, strings: UnsafeRawPointer /* SRArray<SRString> */) {}
+
+            @_cdecl("dictionary")
+            func dictionary(values: Dictionary<String, Int>) {}
+        "#;
+
+        let (module, warnings) = generate_one(source);
+        assert!(module.contains(
+            "swift_rs::swift!(pub fn arrays(ints: &swift_rs::SRArray<swift_rs::Int>, \
+             strings: &swift_rs::SRArray<swift_rs::SRString>));"
+        ));
+        assert!(!module.contains("fn generic"));
+        assert!(!module.contains("fn dictionary"));
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("generic functions aren't supported"));
+        assert!(warnings[1].contains("`Dictionary<String,Int>`"));
+    }
+
+    #[test]
+    fn maps_optionals() {
+        let source = r#"
+            class Thing: NSObject {}
+
+            @_cdecl("optionals")
+            func optionals(
+                name: UnsafeRawPointer? /* SRString? */,
+                count: SROptionalInt
+            ) -> UnsafeMutableRawPointer? /* Optional<Thing> */ {}
+
+            @_cdecl("optional_scalar")
+            func optionalScalar(value: Int?) {}
+        "#;
+
+        let (module, warnings) = generate_one(source);
+        assert!(module.contains(
+            "swift_rs::swift!(pub fn optionals(name: Option<&swift_rs::SRString>, \
+             count: Option<swift_rs::Int>) -> Option<swift_rs::SRObject<Thing>>);"
+        ));
+        assert!(!module.contains("optional_scalar"));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("optional argument type `Int?`"));
+    }
+
+    #[test]
+    fn skips_throws_and_async() {
+        let source = r#"
+            @_cdecl("throwing")
+            func throwing() throws -> Int {}
+
+            @_cdecl("asynchronous")
+            func asynchronous() async {}
+        "#;
+
+        let (module, warnings) = generate_one(source);
+        assert!(declarations(source).is_empty(), "{module}");
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings
+            .iter()
+            .all(|w| w.contains("`async` and `throws` functions aren't supported")));
+    }
+
+    #[test]
+    fn parses_multi_line_signatures() {
+        let source = r#"
+            @_cdecl(
+                "multi_line"
+            )
+            func multiLine(
+                first: Int,  // the first value
+                /* the second value */ second: UnsafePointer<UInt8>,
+                third: UnsafeRawPointer
+                    /* SRData */
+            )
+                -> Int
+            {
+                return first
+            }
+        "#;
+
+        assert_eq!(
+            declarations(source),
+            ["swift_rs::swift!(pub fn multi_line(first: swift_rs::Int, \
+              second: *const swift_rs::UInt8, third: &swift_rs::SRData) -> swift_rs::Int);"]
+        );
+    }
+
+    #[test]
+    fn rejects_unannotated_pointers() {
+        let source = r#"
+            @_cdecl("untyped")
+            func untyped(value: UnsafeRawPointer) -> UnsafeMutableRawPointer {}
+
+            @_cdecl("opaque")
+            func opaque() -> OpaquePointer {}
+        "#;
+
+        let (module, warnings) = generate_one(source);
+        assert!(!module.contains("c_void"));
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("untyped pointer `UnsafeRawPointer`"));
+        assert!(warnings[1].contains("untyped pointer `OpaquePointer`"));
+    }
+
+    #[test]
+    fn skips_duplicates_and_invalid_names() {
+        let source = r#"
+            @_cdecl("twice")
+            func twice() {}
+
+            @_cdecl("twice")
+            func twiceAgain() {}
+
+            @_cdecl("match")
+            func keyword() {}
+        "#;
+
+        let (module, warnings) = generate_one(source);
+        assert_eq!(declarations(source), ["swift_rs::swift!(pub fn twice());"]);
+        assert!(!module.contains("match"));
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("duplicate"));
+        assert!(warnings[1].contains("isn't a valid Rust identifier"));
+    }
+
+    #[test]
+    fn ignores_commented_out_functions() {
+        let source = r#"
+            // @_cdecl("line_comment")
+            // func lineComment() {}
+
+            /* @_cdecl("block_comment")
+               func blockComment() {} */
+
+            @_cdecl("real")
+            func real(label: String = "// not a comment") {}
+        "#;
+
+        let (module, warnings) = generate_one(source);
+        assert!(!module.contains("comment()"));
+        assert!(!module.contains("fn line_comment") && !module.contains("fn block_comment"));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("`real`"));
+    }
+}
@@ -0,0 +1,530 @@
+//! Generates Swift classes from the `#[repr(C)]` structs in a Rust source file,
+//! and Swift enums from its enums,
+//! so that the Rust type is the single definition of a shared type.
+
+use std::collections::HashSet;
+
+use syn::{
+    Expr, ExprLit, ExprUnary, Fields, GenericArgument, Item, ItemEnum, ItemStruct, Lit,
+    PathArguments, Type, UnOp,
+};
+
+const SCALARS: &[(&str, &str)] = &[
+    ("Bool", "Bool"),
+    ("bool", "Bool"),
+    ("Int", "Int"),
+    ("isize", "Int"),
+    ("Int8", "Int8"),
+    ("i8", "Int8"),
+    ("Int16", "Int16"),
+    ("i16", "Int16"),
+    ("Int32", "Int32"),
+    ("i32", "Int32"),
+    ("Int64", "Int64"),
+    ("i64", "Int64"),
+    ("UInt", "UInt"),
+    ("usize", "UInt"),
+    ("UInt8", "UInt8"),
+    ("u8", "UInt8"),
+    ("UInt16", "UInt16"),
+    ("u16", "UInt16"),
+    ("UInt32", "UInt32"),
+    ("u32", "UInt32"),
+    ("UInt64", "UInt64"),
+    ("u64", "UInt64"),
+    ("Float32", "Float"),
+    ("f32", "Float"),
+    ("Float64", "Double"),
+    ("f64", "Double"),
+];
+
+const SWIFT_KEYWORDS: &[&str] = &[
+    "associatedtype",
+    "class",
+    "deinit",
+    "enum",
+    "extension",
+    "func",
+    "import",
+    "init",
+    "inout",
+    "internal",
+    "let",
+    "operator",
+    "private",
+    "protocol",
+    "public",
+    "static",
+    "struct",
+    "subscript",
+    "typealias",
+    "var",
+    "break",
+    "case",
+    "continue",
+    "default",
+    "defer",
+    "do",
+    "else",
+    "fallthrough",
+    "for",
+    "guard",
+    "if",
+    "in",
+    "repeat",
+    "return",
+    "switch",
+    "where",
+    "while",
+    "as",
+    "catch",
+    "false",
+    "is",
+    "nil",
+    "rethrows",
+    "super",
+    "self",
+    "Self",
+    "throw",
+    "throws",
+    "true",
+    "try",
+];
+
+/// Generates a Swift source file with a class for each `#[repr(C)]` struct in `source`,
+/// and an enum for each enum.
+///
+/// Returns the Swift source, and a warning for each type that was skipped.
+pub(super) fn generate(source: &str, source_name: &str) -> Result<(String, Vec<String>), String> {
+    let file = syn::parse_file(source).map_err(|e| e.to_string())?;
+
+    let mut swift = format!(
+        "// Generated by swift-rs from `{source_name}`.\n\
+         // Any changes will be overwritten.\n\
+         \n\
+         import Foundation\n\
+         import SwiftRs\n"
+    );
+    let mut warnings = vec![];
+
+    let types = Types {
+        c_like_enums: file
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Enum(item) if is_c_like(item) && has_repr(&item.attrs, "isize") => {
+                    Some(item.ident.to_string())
+                }
+                _ => None,
+            })
+            .collect(),
+    };
+
+    for item in &file.items {
+        let (ident, generated) = match item {
+            Item::Struct(item) if has_repr(&item.attrs, "C") => (&item.ident, types.class(item)),
+            Item::Enum(item) => (&item.ident, types.enumeration(item)),
+            _ => continue,
+        };
+
+        match generated {
+            Ok(generated) => {
+                swift.push('\n');
+                swift.push_str(&generated);
+            }
+            Err(reason) => warnings.push(format!("Skipping Swift type for `{ident}`: {reason}")),
+        }
+    }
+
+    Ok((swift, warnings))
+}
+
+fn has_repr(attrs: &[syn::Attribute], repr: &str) -> bool {
+    attrs.iter().any(|attr| {
+        let mut found = false;
+        if attr.path().is_ident("repr") {
+            let _ = attr.parse_nested_meta(|meta| {
+                found |= meta.path.is_ident(repr);
+                Ok(())
+            });
+        }
+        found
+    })
+}
+
+fn is_c_like(item: &ItemEnum) -> bool {
+    item.variants
+        .iter()
+        .all(|variant| matches!(variant.fields, Fields::Unit))
+}
+
+fn escape(name: &str) -> String {
+    if SWIFT_KEYWORDS.contains(&name) {
+        format!("`{name}`")
+    } else {
+        name.to_string()
+    }
+}
+
+/// A Swift stored property for a Rust field.
+struct Property {
+    /// Escaped name of the property.
+    name: String,
+    ty: String,
+    /// C-like enums are stored as their raw value, since that's how Rust represents them.
+    raw: bool,
+}
+
+impl Property {
+    /// Name of the stored property, which is only different from `name` for C-like enums.
+    fn storage(&self) -> String {
+        if self.raw {
+            format!("{}RawValue", self.name.trim_matches('`'))
+        } else {
+            self.name.clone()
+        }
+    }
+
+    fn stored_type(&self) -> &str {
+        if self.raw {
+            "Int"
+        } else {
+            &self.ty
+        }
+    }
+
+    /// Converts a value of `ty` named `name` into the stored type.
+    fn store(&self) -> String {
+        if self.raw {
+            format!("{}.rawValue", self.name)
+        } else {
+            self.name.clone()
+        }
+    }
+}
+
+/// The types declared in the Rust source that fields can refer to.
+struct Types {
+    c_like_enums: HashSet<String>,
+}
+
+impl Types {
+    /// Builds the Swift class for a single struct,
+    /// with a stored property for each field in the same order so that their layouts match.
+    fn class(&self, item: &ItemStruct) -> Result<String, String> {
+        if !item.generics.params.is_empty() {
+            return Err("generic structs aren't supported".to_string());
+        }
+
+        let Fields::Named(_) = &item.fields else {
+            return Err("only structs with named fields are supported".to_string());
+        };
+
+        let properties = self.properties(&item.fields)?;
+
+        let mut class = format!("public class {}: NSObject {{\n", item.ident);
+
+        for property in &properties {
+            if property.raw {
+                let (name, ty, storage) = (&property.name, &property.ty, property.storage());
+                class.push_str("    // Stored as its raw value so that Rust can read it\n");
+                class.push_str(&format!("    var {storage}: Int\n"));
+                class.push_str(&format!("    public var {name}: {ty} {{\n"));
+                class.push_str(&format!(
+                    "        get {{ return {ty}(rawValue: {storage})! }}\n"
+                ));
+                class.push_str(&format!(
+                    "        set {{ {storage} = newValue.rawValue }}\n"
+                ));
+                class.push_str("    }\n");
+            } else {
+                class.push_str(&format!(
+                    "    public var {}: {}\n",
+                    property.name, property.ty
+                ));
+            }
+        }
+
+        class.push('\n');
+        class.push_str(&initializer("public init", &properties));
+        class.push_str("}\n");
+
+        Ok(class)
+    }
+
+    /// Builds a Swift enum for a Rust enum.
+    ///
+    /// C-like enums become `Int` enums with the same discriminants.
+    /// Enums with fields are sent to Rust as an `SREnum`,
+    /// with a payload class for each case that has associated values.
+    fn enumeration(&self, item: &ItemEnum) -> Result<String, String> {
+        if !item.generics.params.is_empty() {
+            return Err("generic enums aren't supported".to_string());
+        }
+        if item.variants.is_empty() {
+            return Err("enums without variants aren't supported".to_string());
+        }
+
+        let ident = &item.ident;
+
+        if is_c_like(item) {
+            if !has_repr(&item.attrs, "isize") {
+                return Err("C-like enums must be #[repr(isize)]".to_string());
+            }
+
+            let mut enumeration = format!("public enum {ident}: Int {{\n");
+            let mut discriminant = 0;
+            for variant in &item.variants {
+                if let Some((_, expr)) = &variant.discriminant {
+                    discriminant = integer(expr).ok_or_else(|| {
+                        format!("the discriminant of `{}` isn't an integer", variant.ident)
+                    })?;
+                }
+                enumeration.push_str(&format!(
+                    "    case {} = {discriminant}\n",
+                    case_name(&variant.ident)
+                ));
+                discriminant += 1;
+            }
+            enumeration.push_str("}\n");
+
+            return Ok(enumeration);
+        }
+
+        let cases = item
+            .variants
+            .iter()
+            .map(|variant| {
+                let properties = self.properties(&variant.fields).map_err(|reason| {
+                    format!("variant `{}` is unsupported: {reason}", variant.ident)
+                })?;
+                let labelled = matches!(variant.fields, Fields::Named(_));
+                Ok((
+                    case_name(&variant.ident),
+                    &variant.ident,
+                    properties,
+                    labelled,
+                ))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let mut enumeration = format!("public enum {ident} {{\n");
+        for (case, _, properties, labelled) in &cases {
+            if properties.is_empty() {
+                enumeration.push_str(&format!("    case {case}\n"));
+            } else {
+                let values = properties
+                    .iter()
+                    .map(|property| {
+                        if *labelled {
+                            format!("{}: {}", property.name, property.ty)
+                        } else {
+                            property.ty.clone()
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                enumeration.push_str(&format!("    case {case}({})\n", values.join(", ")));
+            }
+        }
+        enumeration.push_str("}\n\n");
+
+        enumeration.push_str(&format!("extension {ident}: SREnumRepresentable {{\n"));
+        enumeration.push_str("    public var rustTag: Int {\n");
+        enumeration.push_str("        switch self {\n");
+        for (tag, (case, ..)) in cases.iter().enumerate() {
+            enumeration.push_str(&format!("        case .{case}: return {tag}\n"));
+        }
+        enumeration.push_str("        }\n");
+        enumeration.push_str("    }\n\n");
+        enumeration.push_str("    public var rustPayload: NSObject? {\n");
+        enumeration.push_str("        switch self {\n");
+        let mut payloads = String::new();
+        for (case, variant, properties, _) in &cases {
+            if properties.is_empty() {
+                enumeration.push_str(&format!("        case .{case}: return nil\n"));
+                continue;
+            }
+
+            let payload = format!("{ident}{variant}Payload");
+            let names = properties
+                .iter()
+                .map(|property| property.name.as_str())
+                .collect::<Vec<_>>();
+            let arguments = names
+                .iter()
+                .map(|name| format!("{name}: {name}"))
+                .collect::<Vec<_>>();
+            enumeration.push_str(&format!(
+                "        case let .{case}({}): return {payload}({})\n",
+                names.join(", "),
+                arguments.join(", ")
+            ));
+
+            payloads.push_str(&format!("\nclass {payload}: NSObject {{\n"));
+            for property in properties {
+                payloads.push_str(&format!(
+                    "    let {}: {}\n",
+                    property.storage(),
+                    property.stored_type()
+                ));
+            }
+            payloads.push('\n');
+            payloads.push_str(&initializer("init", properties));
+            payloads.push_str("}\n");
+        }
+        enumeration.push_str("        }\n");
+        enumeration.push_str("    }\n");
+        enumeration.push_str("}\n");
+        enumeration.push_str(&payloads);
+
+        Ok(enumeration)
+    }
+
+    fn properties(&self, fields: &Fields) -> Result<Vec<Property>, String> {
+        fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let name = match &field.ident {
+                    Some(ident) => ident.to_string(),
+                    None => format!("_{i}"),
+                };
+                let (ty, raw) = self
+                    .swift_type(&field.ty)
+                    .ok_or_else(|| format!("field `{name}` has an unsupported type"))?;
+                Ok(Property {
+                    name: escape(&name),
+                    ty,
+                    raw,
+                })
+            })
+            .collect()
+    }
+
+    /// The Swift type with the same layout as a field's Rust type,
+    /// and whether it's a C-like enum that's stored as its raw value.
+    fn swift_type(&self, ty: &Type) -> Option<(String, bool)> {
+        let (name, args) = path_type(ty)?;
+
+        if let Some((_, swift)) = SCALARS.iter().find(|(rust, _)| *rust == name) {
+            return Some((swift.to_string(), false));
+        }
+        if args.is_empty() && self.c_like_enums.contains(&name) {
+            return Some((name, true));
+        }
+
+        match name.as_str() {
+            "Option" => self
+                .object_type(args.first()?)
+                .map(|ty| (format!("{ty}?"), false)),
+            _ => self.object_type(ty).map(|ty| (ty, false)),
+        }
+    }
+
+    /// The Swift type of a field that's a reference to an `NSObject`.
+    fn object_type(&self, ty: &Type) -> Option<String> {
+        let (name, args) = path_type(ty)?;
+
+        match name.as_str() {
+            "SRString" | "SRData" => Some(name),
+            // Swift reads the keys and values with `toDictionary()` and `toSet()`
+            "SRDictionary" | "SRSet" => Some(name),
+            "SRObject" => {
+                let (class, args) = path_type(args.first()?)?;
+                args.is_empty().then_some(class)
+            }
+            "SREnum" => {
+                let (enumeration, args) = path_type(args.first()?)?;
+                args.is_empty().then(|| format!("SREnum<{enumeration}>"))
+            }
+            "SRObjectArray" => Some("SRObjectArray".to_string()),
+            "SRWeak" => Some("SRWeak".to_string()),
+            "SRArray" => {
+                let element = args.first()?;
+                let element = match path_type(element)? {
+                    (name, _) if SCALARS.iter().any(|(rust, _)| *rust == name) => {
+                        self.swift_type(element)?.0
+                    }
+                    _ => self.object_type(element)?,
+                };
+                Some(format!("SRArray<{element}>"))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// An initializer with a parameter for each property.
+fn initializer(declaration: &str, properties: &[Property]) -> String {
+    let params = properties
+        .iter()
+        .map(|property| format!("{}: {}", property.name, property.ty))
+        .collect::<Vec<_>>();
+
+    let mut initializer = format!("    {declaration}({}) {{\n", params.join(", "));
+    for property in properties {
+        initializer.push_str(&format!(
+            "        self.{} = {}\n",
+            property.storage(),
+            property.store()
+        ));
+    }
+    initializer.push_str("    }\n");
+
+    initializer
+}
+
+/// Swift's lower camel case name for a Rust variant.
+fn case_name(variant: &syn::Ident) -> String {
+    let variant = variant.to_string();
+    let mut chars = variant.chars();
+    let name = match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => variant,
+    };
+    escape(&name)
+}
+
+/// The value of an integer literal discriminant, which may be negative.
+fn integer(expr: &Expr) -> Option<i128> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(int), ..
+        }) => int.base10_parse().ok(),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => integer(expr).map(|int| -int),
+        Expr::Group(group) => integer(&group.expr),
+        Expr::Paren(paren) => integer(&paren.expr),
+        _ => None,
+    }
+}
+
+/// The last segment of a path type such as `swift_rs::SRArray<Int>`,
+/// along with its generic type arguments.
+fn path_type(ty: &Type) -> Option<(String, Vec<&Type>)> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    if path.qself.is_some() {
+        return None;
+    }
+
+    let segment = path.path.segments.last()?;
+    let args = match &segment.arguments {
+        PathArguments::None => vec![],
+        PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?,
+        PathArguments::Parenthesized(_) => return None,
+    };
+
+    Some((segment.ident.to_string(), args))
+}
@@ -0,0 +1,130 @@
+//! Measures the stored properties of Swift classes,
+//! so that `#[derive(SwiftLayout)]` can compare them with Rust structs.
+
+use std::{collections::HashMap, fs, path::Path, process::Command};
+
+/// Prints the offset and size of each stored property of the classes named in its arguments,
+/// as a line per class starting with its name. Classes that can't be found are followed by `-`.
+const PROBE: &str = r#"@_spi(Reflection) import Swift
+
+func size<T>(_: T.Type) -> Int {
+    return MemoryLayout<T>.size
+}
+
+for name in CommandLine.arguments.dropFirst() {
+    guard let type = _typeByName(name) else {
+        print(name, "-")
+        continue
+    }
+
+    var fields: [String] = []
+    _ = _forEachField(of: type, options: .classType) { _, offset, fieldType, _ in
+        fields.append("\(offset) \(_openExistential(fieldType, do: size))")
+        return true
+    }
+    print(([name] + fields).joined(separator: " "))
+}
+"#;
+
+/// Offset and size of each stored property of a class.
+pub(super) type ClassLayout = Vec<(usize, usize)>;
+
+/// Builds the probe against a package's static archive and runs it,
+/// returning the layout of each of `classes` that could be found.
+///
+/// `classes` must be qualified with their module, like `MyModule.MyClass`.
+pub(super) fn probe(
+    out_dir: &Path,
+    archive: &Path,
+    target: Option<&str>,
+    classes: &[String],
+) -> Result<HashMap<String, ClassLayout>, String> {
+    fs::create_dir_all(out_dir).map_err(|e| e.to_string())?;
+    let source = out_dir.join("layout_probe.swift");
+    let executable = out_dir.join("layout_probe");
+    fs::write(&source, PROBE).map_err(|e| e.to_string())?;
+
+    let mut command = Command::new("swiftc");
+    command.arg(&source).arg("-o").arg(&executable);
+    if let Some(target) = target {
+        command.args(["-target", target]);
+    }
+
+    // The classes aren't referenced by the probe,
+    // so the whole archive has to be loaded for their metadata to be included
+    if cfg!(target_vendor = "apple") {
+        command
+            .args(["-Xlinker", "-force_load", "-Xlinker"])
+            .arg(archive);
+    } else {
+        command
+            .args(["-Xlinker", "--whole-archive"])
+            .arg(archive)
+            .args(["-Xlinker", "--no-whole-archive", "-lFoundation"]);
+    }
+
+    let output = command
+        .output()
+        .map_err(|e| format!("failed to run swiftc: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "failed to compile the layout probe:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    let output = Command::new(&executable)
+        .args(classes)
+        .output()
+        .map_err(|e| format!("failed to run the layout probe: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "the layout probe failed:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    let mut layouts = HashMap::new();
+
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let mut words = line.split_whitespace();
+        let Some(name) = words.next() else {
+            continue;
+        };
+
+        let numbers = words
+            .map(|word| word.parse::<usize>().ok())
+            .collect::<Option<Vec<_>>>();
+        if let Some(numbers) = numbers {
+            let fields = numbers
+                .chunks(2)
+                .map(|field| (field[0], field[1]))
+                .collect();
+            layouts.insert(name.to_string(), fields);
+        }
+    }
+
+    Ok(layouts)
+}
+
+/// Writes a class's layout as a Rust expression for `#[derive(SwiftLayout)]` to include,
+/// with `None` meaning that it couldn't be measured.
+/// The file is named after the class's module and name, such as `MyModule.MyClass.rs`.
+pub(super) fn write_layout(
+    layouts_dir: &Path,
+    qualified_class: &str,
+    layout: Option<&ClassLayout>,
+) -> std::io::Result<()> {
+    let contents = match layout {
+        Some(fields) => {
+            let fields = fields
+                .iter()
+                .map(|(offset, size)| format!("({offset}, {size})"))
+                .collect::<Vec<_>>();
+            format!("Some(&[{}])", fields.join(", "))
+        }
+        None => "None".to_string(),
+    };
+
+    fs::write(layouts_dir.join(format!("{qualified_class}.rs")), contents)
+}
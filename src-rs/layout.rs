@@ -0,0 +1,40 @@
+/// A `#[repr(C)]` struct whose layout should match the stored properties of a Swift class.
+///
+/// Implement this with `#[derive(SwiftLayout)]` from the `derive` feature,
+/// which also checks the struct against the Swift class when the crate is compiled.
+/// This requires [`SwiftLinker::with_layout_checks`](crate::SwiftLinker::with_layout_checks)
+/// in your build script, which measures the Swift classes in your packages.
+///
+/// ```ignore
+/// use swift_rs::{Int, SRString, SwiftLayout};
+///
+/// // Fails to compile unless the Swift class `Volume` has stored properties
+/// // with the same offsets and sizes, in the same order.
+/// #[derive(SwiftLayout)]
+/// #[repr(C)]
+/// struct Volume {
+///     name: SRString,
+///     total_capacity: Int,
+/// }
+/// ```
+///
+/// The Swift class is assumed to have the same name as the struct,
+/// which can be changed with `#[swift_layout(class = "OtherName")]`.
+/// If more than one module has a class with that name,
+/// the module must be included too, as in `#[swift_layout(class = "MyModule.OtherName")]`.
+pub trait SwiftLayout {
+    /// Name of the Swift class, as given to `#[swift_layout(class = "...")]`.
+    const SWIFT_CLASS: &'static str;
+
+    /// Layout of each of the struct's fields inside the Swift object, in declaration order.
+    const FIELDS: &'static [FieldLayout];
+}
+
+/// Where a field is stored inside a Swift object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldLayout {
+    pub name: &'static str,
+    /// Offset from the start of the object, including its header.
+    pub offset: usize,
+    pub size: usize,
+}
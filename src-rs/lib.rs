@@ -2,16 +2,24 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 mod autorelease;
+mod layout;
+mod main_thread;
 mod swift;
 mod swift_arg;
 mod swift_ret;
 mod types;
 
+pub use layout::*;
+pub use main_thread::*;
 pub use swift::*;
 pub use swift_arg::*;
 pub use swift_ret::*;
 pub use types::*;
 
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+pub use swift_rs_macros::{SwiftEnum, SwiftLayout, SwiftObject};
+
 #[cfg(feature = "build")]
 #[cfg_attr(docsrs, doc(cfg(feature = "build")))]
 mod build;
@@ -0,0 +1,42 @@
+use std::marker::PhantomData;
+
+use crate::swift;
+
+/// Proof that the current thread is the main thread.
+///
+/// Functions declared with `#[main_thread]` in [`swift!`](crate::swift) take a
+/// `MainThreadMarker` as their first argument. Since it can't be sent to other threads,
+/// those functions can only be called from the main thread.
+///
+/// ```
+/// use swift_rs::MainThreadMarker;
+///
+/// if let Some(mtm) = MainThreadMarker::new() {
+///     // can call main-thread-only functions here
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MainThreadMarker {
+    _not_send: PhantomData<*mut ()>,
+}
+
+impl MainThreadMarker {
+    /// Returns a `MainThreadMarker` if called from the main thread.
+    pub fn new() -> Option<Self> {
+        if unsafe { swift::is_main_thread() } {
+            Some(unsafe { Self::new_unchecked() })
+        } else {
+            None
+        }
+    }
+
+    /// Creates a `MainThreadMarker` without checking the current thread.
+    ///
+    /// # Safety
+    /// Must be called from the main thread.
+    pub unsafe fn new_unchecked() -> Self {
+        Self {
+            _not_send: PhantomData,
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::ffi::c_void;
+use std::{ffi::c_void, mem::ManuallyDrop};
 
 use crate::*;
 
@@ -14,6 +14,27 @@ impl<'a, T: SwiftObject> SwiftRef<'a, T> {
 }
 
 /// A type that is represented as an `NSObject` in Swift.
+///
+/// With the `derive` feature, `#[derive(SwiftObject)]` implements this and [`SwiftArg`]
+/// for `#[repr(transparent)]` wrappers around another `SwiftObject`,
+/// so that they can be used with [`swift!`] in place of the object they wrap:
+///
+/// ```ignore
+/// use swift_rs::{swift, Int, SRObject, SRString, SwiftObject};
+///
+/// #[repr(C)]
+/// struct PhotoData {
+///     name: SRString,
+///     width: Int,
+/// }
+///
+/// #[derive(SwiftObject)]
+/// #[repr(transparent)]
+/// pub struct Photo(SRObject<PhotoData>);
+///
+/// swift!(fn load_photo(path: &SRString) -> Option<Photo>);
+/// swift!(fn photo_width(photo: &Photo) -> Int);
+/// ```
 pub trait SwiftObject {
     type Shape;
 
@@ -50,10 +71,78 @@ pub trait SwiftObject {
     }
 }
 
+/// Gives up Rust's ownership of an object passed to Swift as an `#[owned]` argument.
+/// This function should never be called manually,
+/// instead you should rely on the [`swift!`] macro to call it for you.
+#[doc(hidden)]
+pub fn owned_arg<T: SwiftObject>(object: T) -> ManuallyDrop<T> {
+    ManuallyDrop::new(object)
+}
+
 swift!(pub(crate) fn retain_object(obj: *const c_void));
 swift!(pub(crate) fn release_object(obj: *const c_void));
+swift!(pub(crate) fn retain_count(obj: *const c_void) -> Int);
+swift!(pub(crate) fn is_main_thread() -> Bool);
 swift!(pub(crate) fn data_from_bytes(data: *const u8, size: Int) -> SRData);
+swift!(pub(crate) fn data_from_rust(
+    data: *const u8,
+    size: Int,
+    context: *mut c_void,
+    destroy: *const c_void
+) -> SRData);
 swift!(pub(crate) fn string_from_bytes(data: *const u8, size: Int) -> SRString);
+swift!(pub(crate) fn array_from_bool(data: *const Bool, size: Int) -> SRArray<Bool>);
+swift!(pub(crate) fn array_from_int(data: *const Int, size: Int) -> SRArray<Int>);
+swift!(pub(crate) fn array_from_int8(data: *const Int8, size: Int) -> SRArray<Int8>);
+swift!(pub(crate) fn array_from_int16(data: *const Int16, size: Int) -> SRArray<Int16>);
+swift!(pub(crate) fn array_from_int32(data: *const Int32, size: Int) -> SRArray<Int32>);
+swift!(pub(crate) fn array_from_int64(data: *const Int64, size: Int) -> SRArray<Int64>);
+swift!(pub(crate) fn array_from_uint(data: *const UInt, size: Int) -> SRArray<UInt>);
+swift!(pub(crate) fn array_from_uint8(data: *const UInt8, size: Int) -> SRArray<UInt8>);
+swift!(pub(crate) fn array_from_uint16(data: *const UInt16, size: Int) -> SRArray<UInt16>);
+swift!(pub(crate) fn array_from_uint32(data: *const UInt32, size: Int) -> SRArray<UInt32>);
+swift!(pub(crate) fn array_from_uint64(data: *const UInt64, size: Int) -> SRArray<UInt64>);
+swift!(pub(crate) fn array_from_float32(data: *const Float32, size: Int) -> SRArray<Float32>);
+swift!(pub(crate) fn array_from_float64(data: *const Float64, size: Int) -> SRArray<Float64>);
+swift!(pub(crate) fn mutable_buffer_new(
+    stride: Int,
+    alignment: Int,
+    capacity: Int
+) -> SRObject<SRMutableBufferImpl>);
+swift!(pub(crate) fn mutable_buffer_reserve(buffer: &SRObject<SRMutableBufferImpl>, additional: Int));
+swift!(pub(crate) fn data_from_mutable_buffer(buffer: &SRObject<SRMutableBufferImpl>) -> SRData);
+swift!(pub(crate) fn array_from_bool_buffer(buffer: &SRObject<SRMutableBufferImpl>) -> SRArray<Bool>);
+swift!(pub(crate) fn array_from_int_buffer(buffer: &SRObject<SRMutableBufferImpl>) -> SRArray<Int>);
+swift!(pub(crate) fn array_from_int8_buffer(buffer: &SRObject<SRMutableBufferImpl>) -> SRArray<Int8>);
+swift!(pub(crate) fn array_from_int16_buffer(buffer: &SRObject<SRMutableBufferImpl>) -> SRArray<Int16>);
+swift!(pub(crate) fn array_from_int32_buffer(buffer: &SRObject<SRMutableBufferImpl>) -> SRArray<Int32>);
+swift!(pub(crate) fn array_from_int64_buffer(buffer: &SRObject<SRMutableBufferImpl>) -> SRArray<Int64>);
+swift!(pub(crate) fn array_from_uint_buffer(buffer: &SRObject<SRMutableBufferImpl>) -> SRArray<UInt>);
+swift!(pub(crate) fn array_from_uint8_buffer(buffer: &SRObject<SRMutableBufferImpl>) -> SRArray<UInt8>);
+swift!(pub(crate) fn array_from_uint16_buffer(buffer: &SRObject<SRMutableBufferImpl>) -> SRArray<UInt16>);
+swift!(pub(crate) fn array_from_uint32_buffer(buffer: &SRObject<SRMutableBufferImpl>) -> SRArray<UInt32>);
+swift!(pub(crate) fn array_from_uint64_buffer(buffer: &SRObject<SRMutableBufferImpl>) -> SRArray<UInt64>);
+swift!(pub(crate) fn array_from_float32_buffer(buffer: &SRObject<SRMutableBufferImpl>) -> SRArray<Float32>);
+swift!(pub(crate) fn array_from_float64_buffer(buffer: &SRObject<SRMutableBufferImpl>) -> SRArray<Float64>);
+swift!(pub(crate) fn array_from_strings(data: *const c_void, size: Int) -> SRArray<SRString>);
+swift!(pub(crate) fn array_from_data(data: *const c_void, size: Int) -> SRArray<SRData>);
+swift!(pub(crate) fn array_from_objects(data: *const c_void, size: Int) -> SRArray<SRObject<()>>);
+swift!(pub(crate) fn object_array_from_objects(data: *const c_void, size: Int) -> SRObjectArray<()>);
+swift!(pub(crate) fn dictionary_from_arrays(
+    keys: &SRArray<()>,
+    values: &SRArray<()>
+) -> SRObject<()>);
+swift!(pub(crate) fn set_from_array(elements: &SRArray<()>) -> SRObject<()>);
+swift!(pub(crate) fn weak_new(object: &SRObject<()>) -> SRObject<SRWeakImpl>);
+swift!(pub(crate) fn weak_upgrade(weak: &SRObject<SRWeakImpl>) -> Option<SRObject<()>>);
+swift!(pub(crate) fn closure_new(
+    context: *mut c_void,
+    call: *const c_void,
+    destroy: *const c_void,
+    argument_sizes: *const Int,
+    argument_count: Int
+) -> SRObject<SRClosureImpl>);
+swift!(pub(crate) fn task_cancel(task: &SRObject<SRTaskImpl>));
 
 /// Declares a function defined in a swift library.
 /// As long as this macro is used, retain counts of arguments
@@ -79,23 +168,189 @@ swift!(pub(crate) fn string_from_bytes(data: *const u8, size: Int) -> SRString);
 /// that represents the actual Swift function. This is done in order to restrict the types
 /// that can be used as arguments and return types, and to ensure that retain counts of returned
 /// values are appropriately balanced.
+///
+/// On platforms without an Objective-C runtime (ie. Linux) `@_cdecl` functions can't use class
+/// types directly, so the Swift side should accept and return `UnsafeRawPointer`s using the
+/// `fromRust` and `toRust` helpers from `SwiftRs`. These pass objects with the same ownership
+/// that [`swift!`] expects on every platform.
+///
+/// # Optional scalars
+///
+/// `Option`s of scalars such as `Option<Int>` are passed as a tag followed by the value,
+/// which Swift declares as `SROptionalInt`, `SROptionalDouble` and so on.
+/// These are converted to and from Swift optionals with `toRust` and `fromRust`:
+///
+/// ```swift
+/// @_cdecl("parse_int")
+/// func parseInt(string: UnsafeRawPointer, radix: SROptionalInt) -> SROptionalInt {
+///     let string: SRString = fromRust(string)
+///     return toRust(Int(string.toString(), radix: fromRust(radix) ?? 10))
+/// }
+/// ```
+///
+/// ```
+/// use swift_rs::*;
+///
+/// swift!(fn parse_int(string: &SRString, radix: Option<Int>) -> Option<Int>);
+///
+/// assert_eq!(unsafe { parse_int(&"42".into(), None) }, Some(42));
+/// assert_eq!(unsafe { parse_int(&"ff".into(), Some(16)) }, Some(255));
+/// assert_eq!(unsafe { parse_int(&"forty two".into(), None) }, None);
+/// ```
+///
+/// # Ownership
+///
+/// By default object arguments are borrowed by Swift for the duration of the call,
+/// and returned objects are expected to come from `toRust`.
+/// Swift functions that follow different conventions can be declared by annotating
+/// their arguments and return type:
+///
+/// - `#[owned]` on an argument moves it into Swift, which is then responsible for releasing it,
+///   such as with `Unmanaged.takeRetainedValue()`.
+/// - `#[owned]` on the return type is for objects that are already retained for the caller,
+///   such as with `Unmanaged.passRetained(...)`.
+/// - `#[unretained]` on the return type is for objects that aren't retained or autoreleased,
+///   such as with `Unmanaged.passUnretained(...)`, and so must be kept alive by Swift.
+///
+/// ```
+/// use swift_rs::*;
+///
+/// swift!(fn consume_string(#[owned] string: SRString) -> Int);
+/// swift!(fn retained_string() -> #[owned] SRString);
+/// swift!(fn unretained_string() -> #[unretained] SRString);
+///
+/// let length = unsafe { consume_string("test".into()) };
+/// assert_eq!(length, 4);
+///
+/// assert_eq!(unsafe { retained_string() }.as_str(), "retained");
+/// assert_eq!(unsafe { unretained_string() }.as_str(), "unretained");
+/// ```
+///
+/// # Main thread
+///
+/// Functions that may only be called from the main thread, such as most AppKit and UIKit APIs,
+/// can be annotated with `#[main_thread]`.
+/// The declared function then takes a [`MainThreadMarker`] before its other arguments:
+///
+/// ```
+/// use swift_rs::*;
+///
+/// swift!(#[main_thread] fn show_alert(message: &SRString));
+///
+/// if let Some(mtm) = MainThreadMarker::new() {
+///     unsafe { show_alert(mtm, &"hello".into()) };
+/// }
+/// ```
+///
+/// # Async functions
+///
+/// Functions declared as `async fn` return a [`SwiftFuture`] that resolves with the return value.
+/// The Swift function receives an extra completion [`SRClosure`] argument
+/// and should return an `SRTask`, as described in [`SwiftFuture`].
+///
+/// ```
+/// use swift_rs::*;
+/// # fn block_on<F: std::future::Future>(future: F) -> F::Output {
+/// #     use std::task::{Context, Poll, Wake};
+/// #     struct ThreadWaker(std::thread::Thread);
+/// #     impl Wake for ThreadWaker {
+/// #         fn wake(self: std::sync::Arc<Self>) { self.0.unpark() }
+/// #     }
+/// #     let waker = std::sync::Arc::new(ThreadWaker(std::thread::current())).into();
+/// #     let mut future = std::pin::pin!(future);
+/// #     loop {
+/// #         match future.as_mut().poll(&mut Context::from_waker(&waker)) {
+/// #             Poll::Ready(value) => return value,
+/// #             Poll::Pending => std::thread::park(),
+/// #         }
+/// #     }
+/// # }
+///
+/// swift!(async fn async_echo(string: &SRString) -> SRString);
+///
+/// let string: SRString = "test".into();
+/// let result = block_on(unsafe { async_echo(&string) });
+///
+/// assert_eq!(result.as_str(), string.as_str())
+/// ```
 #[macro_export]
 macro_rules! swift {
-    ($vis:vis fn $name:ident $(<$($lt:lifetime),+>)? ($($arg:ident: $arg_ty:ty),*) $(-> $ret:ty)?) => {
+    (@arg $arg:ident) => {
+        let $arg = $crate::SwiftArg::as_arg(&$arg);
+    };
+    (@arg $arg:ident owned) => {
+        let $arg = $crate::owned_arg($arg);
+        let $arg = $crate::SwiftArg::as_arg(&*$arg);
+    };
+    (@retain $res:ident) => {
+        // Objects are returned autoreleased where there's an Objective-C runtime,
+        // and already retained for the caller everywhere else.
+        #[cfg(target_vendor = "apple")]
+        $crate::SwiftRet::retain(&$res);
+    };
+    (@retain $res:ident owned) => {};
+    (@retain $res:ident unretained) => {
+        $crate::SwiftRet::retain(&$res);
+    };
+    (#[main_thread] $vis:vis async fn $name:ident $(<$($lt:lifetime),+>)? ($($(#[$arg_attr:ident])? $arg:ident: $arg_ty:ty),*)) => {
+        $crate::swift!(#[main_thread] $vis async fn $name $(<$($lt),*>)? ($($(#[$arg_attr])? $arg: $arg_ty),*) -> ());
+    };
+    (#[main_thread] $vis:vis async fn $name:ident $(<$($lt:lifetime),+>)? ($($(#[$arg_attr:ident])? $arg:ident: $arg_ty:ty),*) -> $ret:ty) => {
+        $vis unsafe fn $name $(<$($lt),*>)? (_: $crate::MainThreadMarker, $($arg: $arg_ty),*) -> $crate::SwiftFuture<$ret> {
+            $crate::swift!(async fn $name $(<$($lt),*>)? ($($(#[$arg_attr])? $arg: $arg_ty),*) -> $ret);
+
+            $name($($arg),*)
+        }
+    };
+    (#[main_thread] $vis:vis fn $name:ident $(<$($lt:lifetime),+>)? ($($(#[$arg_attr:ident])? $arg:ident: $arg_ty:ty),*) $(-> $(#[$ret_attr:ident])? $ret:ty)?) => {
+        $vis unsafe fn $name $(<$($lt),*>)? (_: $crate::MainThreadMarker, $($arg: $arg_ty),*) $(-> $ret)? {
+            $crate::swift!(fn $name $(<$($lt),*>)? ($($(#[$arg_attr])? $arg: $arg_ty),*) $(-> $(#[$ret_attr])? $ret)?);
+
+            $name($($arg),*)
+        }
+    };
+    ($vis:vis async fn $name:ident $(<$($lt:lifetime),+>)? ($($(#[$arg_attr:ident])? $arg:ident: $arg_ty:ty),*)) => {
+        $crate::swift!($vis async fn $name $(<$($lt),*>)? ($($(#[$arg_attr])? $arg: $arg_ty),*) -> ());
+    };
+    ($vis:vis async fn $name:ident $(<$($lt:lifetime),+>)? ($($(#[$arg_attr:ident])? $arg:ident: $arg_ty:ty),*) -> $ret:ty) => {
+        $vis unsafe fn $name $(<$($lt),*>)? ($($arg: $arg_ty),*) -> $crate::SwiftFuture<$ret> {
+            extern "C" {
+                fn $name $(<$($lt),*>)? (
+                    $($arg: <$arg_ty as $crate::SwiftArg>::ArgType,)*
+                    completion: <&$crate::SRClosure<($ret,)> as $crate::SwiftArg>::ArgType
+                ) -> $crate::SRObject<$crate::SRTaskImpl>;
+            }
+
+            let (future, completion) = $crate::SwiftFuture::<$ret>::new();
+
+            let task = {
+                $($crate::swift!(@arg $arg $($arg_attr)?);)*
+                let completion = $crate::SwiftArg::as_arg(&completion);
+
+                $name($($arg,)* completion)
+            };
+
+            // Same ownership as any other returned object
+            $crate::swift!(@retain task);
+
+            future.with_task(task)
+        }
+    };
+    ($vis:vis fn $name:ident $(<$($lt:lifetime),+>)? ($($(#[$arg_attr:ident])? $arg:ident: $arg_ty:ty),*) $(-> $(#[$ret_attr:ident])? $ret:ty)?) => {
         $vis unsafe fn $name $(<$($lt),*>)? ($($arg: $arg_ty),*) $(-> $ret)? {
             extern "C" {
-                fn $name $(<$($lt),*>)? ($($arg: <$arg_ty as $crate::SwiftArg>::ArgType),*) $(-> $ret)?;
+                fn $name $(<$($lt),*>)? ($($arg: <$arg_ty as $crate::SwiftArg>::ArgType),*) $(-> <$ret as $crate::SwiftRet>::RetType)?;
             }
 
             let res = {
-                $(let $arg = $crate::SwiftArg::as_arg(&$arg);)*
+                $($crate::swift!(@arg $arg $($arg_attr)?);)*
 
                 $name($($arg),*)
             };
 
-            $crate::SwiftRet::retain(&res);
+            $crate::swift!(@retain res $($($ret_attr)?)?);
 
-            res
+            $crate::SwiftRet::from_ret(res)
         }
     };
 }
@@ -77,9 +77,25 @@ primitive_impl!(
     ()
 );
 
+macro_rules! optional_impl {
+    ($($t:ty),+) => {
+        $(impl<'a> SwiftArg<'a> for Option<$t> {
+            type ArgType = SROptional<$t>;
+
+            unsafe fn as_arg(&'a self) -> Self::ArgType {
+                (*self).into()
+            }
+        })+
+    };
+}
+
+optional_impl!(
+    Bool, Int, Int8, Int16, Int32, Int64, UInt, UInt8, UInt16, UInt32, UInt64, Float32, Float64
+);
+
 macro_rules! ref_impl {
     ($($t:ident $(<$($gen:ident),+>)?),+) => {
-        $(impl<'a $($(, $gen: 'a),+)?> SwiftArg<'a> for $t$(<$($gen),+>)? {
+        $(impl<'a $($(, $gen: 'a)+)?> SwiftArg<'a> for $t$(<$($gen),+>)? {
             type ArgType = SwiftRef<'a, $t$(<$($gen),+>)?>;
 
             unsafe fn as_arg(&'a self) -> Self::ArgType {
@@ -89,7 +105,31 @@ macro_rules! ref_impl {
     };
 }
 
-ref_impl!(SRObject<T>, SRArray<T>, SRData, SRString);
+ref_impl!(
+    SRObject<T>,
+    SRArray<T>,
+    SRDictionary<K, V>,
+    SREnum<T>,
+    SRSet<T>,
+    SRWeak<T>,
+    SRData,
+    SRString,
+    SRClosure<Args>
+);
+
+#[cfg(feature = "json")]
+ref_impl!(SwiftJson<T>);
+
+impl<'a, T: SwiftObject + 'a> SwiftArg<'a> for Option<&T>
+where
+    T::Shape: 'a,
+{
+    type ArgType = Option<SwiftRef<'a, T>>;
+
+    unsafe fn as_arg(&'a self) -> Self::ArgType {
+        self.map(|v| v.swift_ref())
+    }
+}
 
 impl<'a, T: SwiftArg<'a>> SwiftArg<'a> for &T {
     type ArgType = T::ArgType;
@@ -5,6 +5,21 @@ use std::ffi::c_void;
 /// For types that are objects which need extra retains,
 /// the [`retain`](SwiftRet::retain) function will be re-implemented.
 pub trait SwiftRet {
+    /// The type that the Swift function actually returns.
+    /// For most types this is just `Self`,
+    /// but types like [`Result`] are converted from a Swift object.
+    type RetType: SwiftRet;
+
+    /// Converts the value returned by Swift into `Self`,
+    /// after it has been [`retain`](SwiftRet::retain)ed if necessary.
+    ///
+    /// This function is called within the [`swift!`] macro.
+    ///
+    /// # Safety
+    /// Just don't use this.
+    /// Let [`swift!`] handle it.
+    unsafe fn from_ret(ret: Self::RetType) -> Self;
+
     /// Adds a retain to the value if possible
     ///
     /// # Safety
@@ -16,6 +31,11 @@ pub trait SwiftRet {
 macro_rules! primitive_impl {
     ($($t:ty),+) => {
         $(impl SwiftRet for $t {
+            type RetType = $t;
+
+            unsafe fn from_ret(ret: Self::RetType) -> Self {
+                ret
+            }
         })+
     };
 }
@@ -65,7 +85,37 @@ primitive_impl!(
     *mut Float64
 );
 
+macro_rules! optional_impl {
+    ($($t:ty),+) => {
+        $(impl SwiftRet for Option<$t> {
+            type RetType = SROptional<$t>;
+
+            unsafe fn from_ret(ret: Self::RetType) -> Self {
+                ret.into()
+            }
+        }
+
+        impl SwiftRet for SROptional<$t> {
+            type RetType = Self;
+
+            unsafe fn from_ret(ret: Self::RetType) -> Self {
+                ret
+            }
+        })+
+    };
+}
+
+optional_impl!(
+    Bool, Int, Int8, Int16, Int32, Int64, UInt, UInt8, UInt16, UInt32, UInt64, Float32, Float64
+);
+
 impl<T: SwiftObject> SwiftRet for Option<T> {
+    type RetType = Self;
+
+    unsafe fn from_ret(ret: Self::RetType) -> Self {
+        ret
+    }
+
     unsafe fn retain(&self) {
         if let Some(v) = self {
             v.retain()
@@ -74,7 +124,71 @@ impl<T: SwiftObject> SwiftRet for Option<T> {
 }
 
 impl<T: SwiftObject> SwiftRet for T {
+    type RetType = Self;
+
+    unsafe fn from_ret(ret: Self::RetType) -> Self {
+        ret
+    }
+
     unsafe fn retain(&self) {
         (*self).retain()
     }
 }
+
+impl<T: SwiftObject> SwiftRet for Result<T, SwiftError> {
+    type RetType = SRObject<SRResultImpl>;
+
+    unsafe fn from_ret(ret: Self::RetType) -> Self {
+        ret.into_result()?.ok_or_else(|| SwiftError {
+            type_name: "SwiftRs.SRResult".into(),
+            description: format!(
+                "Swift returned a successful SRResult without a value, expected {}",
+                std::any::type_name::<T>()
+            ),
+            domain: None,
+            code: None,
+        })
+    }
+}
+
+impl<T: SwiftObject> SwiftRet for Result<Option<T>, SwiftError> {
+    type RetType = SRObject<SRResultImpl>;
+
+    unsafe fn from_ret(ret: Self::RetType) -> Self {
+        ret.into_result()
+    }
+}
+
+impl SwiftRet for Result<(), SwiftError> {
+    type RetType = SRObject<SRResultImpl>;
+
+    unsafe fn from_ret(ret: Self::RetType) -> Self {
+        ret.into_result::<SRObject<()>>().map(|_| ())
+    }
+}
+
+macro_rules! scalar_result_impl {
+    ($($t:ty),+) => {
+        $(impl SwiftRet for Result<$t, SwiftError> {
+            type RetType = SRObject<SRResultImpl>;
+
+            unsafe fn from_ret(ret: Self::RetType) -> Self {
+                ret.into_scalar_result()
+            }
+        }
+
+        impl SwiftRet for Result<Option<$t>, SwiftError> {
+            type RetType = SRObject<SRResultImpl>;
+
+            unsafe fn from_ret(ret: Self::RetType) -> Self {
+                ret.into_scalar_result::<SROptional<$t>>().map(Into::into)
+            }
+        }
+
+        const _: () = assert!(std::mem::size_of::<SROptional<$t>>() <= SRResultImpl::SCALAR_SIZE);)+
+    };
+}
+
+scalar_result_impl!(
+    Bool, Int, Int8, Int16, Int32, Int64, UInt, UInt8, UInt16, UInt32, UInt64, Float32, Float64
+);
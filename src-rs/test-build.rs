@@ -7,6 +7,7 @@ mod build;
 
 fn main() {
     println!("cargo:rerun-if-env-changed=TEST_SWIFT_RS");
+    println!("cargo:rustc-check-cfg=cfg(test_swift_rs)");
 
     #[cfg(feature = "build")]
     if std::env::var("TEST_SWIFT_RS").unwrap_or_else(|_| "false".into()) == "true" {
@@ -16,6 +17,12 @@ fn main() {
             .with_ios("11")
             .with_visionos("1")
             .with_package("test-swift", "tests/swift-pkg")
+            .with_bindings("swift_bindings.rs")
+            .with_swift_classes("tests/swift-pkg/shared.rs", "tests/swift-pkg/shared.swift")
+            .with_layout_checks()
             .link();
+
+        // Layouts are only available to `#[derive(SwiftLayout)]` once the package is built
+        println!("cargo:rustc-cfg=test_swift_rs");
     }
 }
@@ -1,6 +1,9 @@
-use std::{ops::Deref, ptr::NonNull};
+use std::{ffi::c_void, ops::Deref, ptr::NonNull};
 
-use crate::swift::SwiftObject;
+use crate::{
+    swift::{self, SwiftObject},
+    *,
+};
 
 use super::SRObject;
 
@@ -59,15 +62,44 @@ pub struct SRArrayImpl<T> {
 /// assert_eq!(data.array.as_slice(), &[4, 5, 6]);
 /// ```
 /// [_corresponding Swift code_](https://github.com/Brendonovich/swift-rs/blob/07269e511f1afb71e2fcfa89ca5d7338bceb20e8/tests/swift-pkg/doctests.swift#L32)
+///
+/// ## Creating from Rust
+///
+/// Arrays of scalars, [`SRString`], [`SRData`] and [`SRObject`]
+/// can be created from slices, `Vec`s and iterators.
+/// This allocates a Swift `[T]` and copies the elements into it,
+/// retaining any objects.
+/// [`SRObjectArray`] can be created in the same way.
+///
+/// ```rust
+/// use swift_rs::{swift, Float64, SRArray};
+///
+/// swift!(fn average(samples: &SRArray<Float64>) -> Float64);
+///
+/// let samples: SRArray<Float64> = vec![1.0, 2.0, 6.0].into();
+///
+/// assert_eq!(unsafe { average(&samples) }, 3.0);
+/// ```
 #[repr(transparent)]
-pub struct SRArray<T>(SRObject<SRArrayImpl<T>>);
+pub struct SRArray<T>(pub(crate) SRObject<SRArrayImpl<T>>);
 
 impl<T> SRArray<T> {
     pub fn as_slice(&self) -> &[T] {
         self.0.as_slice()
     }
+
+    /// Reinterprets the elements as a different type,
+    /// transferring ownership of the array's retain.
+    pub(crate) fn cast<U>(self) -> SRArray<U> {
+        SRArray(self.0.cast())
+    }
 }
 
+// Arrays are immutable once they've been passed to Rust,
+// but clones share elements so they must also be Sync
+unsafe impl<T: Send + Sync> Send for SRArray<T> {}
+unsafe impl<T: Send + Sync> Sync for SRArray<T> {}
+
 impl<T> SwiftObject for SRArray<T> {
     type Shape = SRArrayImpl<T>;
 
@@ -84,6 +116,101 @@ impl<T> Deref for SRArray<T> {
     }
 }
 
+/// Types that [`SRArray`]s can be created from in Rust.
+pub trait SRArrayElement: Sized {
+    #[doc(hidden)]
+    fn array_from_slice(slice: &[Self]) -> SRArray<Self>;
+}
+
+macro_rules! scalar_impl {
+    ($($t:ty => $fn:ident),+) => {
+        $(impl SRArrayElement for $t {
+            fn array_from_slice(slice: &[Self]) -> SRArray<Self> {
+                unsafe { swift::$fn(slice.as_ptr(), slice.len() as Int) }
+            }
+        })+
+    };
+}
+
+scalar_impl!(
+    Bool => array_from_bool,
+    Int => array_from_int,
+    Int8 => array_from_int8,
+    Int16 => array_from_int16,
+    Int32 => array_from_int32,
+    Int64 => array_from_int64,
+    UInt => array_from_uint,
+    UInt8 => array_from_uint8,
+    UInt16 => array_from_uint16,
+    UInt32 => array_from_uint32,
+    UInt64 => array_from_uint64,
+    Float32 => array_from_float32,
+    Float64 => array_from_float64
+);
+
+// Objects are passed to Swift as an array of pointers,
+// each of which is retained by the Swift array
+
+impl SRArrayElement for SRString {
+    fn array_from_slice(slice: &[Self]) -> SRArray<Self> {
+        unsafe { swift::array_from_strings(slice.as_ptr() as *const c_void, slice.len() as Int) }
+    }
+}
+
+impl SRArrayElement for SRData {
+    fn array_from_slice(slice: &[Self]) -> SRArray<Self> {
+        unsafe { swift::array_from_data(slice.as_ptr() as *const c_void, slice.len() as Int) }
+    }
+}
+
+impl<T> SRArrayElement for SRObject<T> {
+    fn array_from_slice(slice: &[Self]) -> SRArray<Self> {
+        let array = unsafe {
+            swift::array_from_objects(slice.as_ptr() as *const c_void, slice.len() as Int)
+        };
+        SRArray(array.0.cast())
+    }
+}
+
+impl<T: SRArrayElement> From<&[T]> for SRArray<T> {
+    fn from(value: &[T]) -> Self {
+        T::array_from_slice(value)
+    }
+}
+
+impl<T: SRArrayElement> From<Vec<T>> for SRArray<T> {
+    fn from(value: Vec<T>) -> Self {
+        T::array_from_slice(&value)
+    }
+}
+
+impl<T: SRArrayElement> FromIterator<T> for SRArray<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        T::array_from_slice(&iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<T> From<&[SRObject<T>]> for SRObjectArray<T> {
+    fn from(value: &[SRObject<T>]) -> Self {
+        let array = unsafe {
+            swift::object_array_from_objects(value.as_ptr() as *const c_void, value.len() as Int)
+        };
+        array.cast()
+    }
+}
+
+impl<T> From<Vec<SRObject<T>>> for SRObjectArray<T> {
+    fn from(value: Vec<SRObject<T>>) -> Self {
+        value.as_slice().into()
+    }
+}
+
+impl<T> FromIterator<SRObject<T>> for SRObjectArray<T> {
+    fn from_iter<I: IntoIterator<Item = SRObject<T>>>(iter: I) -> Self {
+        iter.into_iter().collect::<Vec<_>>().into()
+    }
+}
+
 impl<T> SRArrayImpl<T> {
     pub fn as_slice(&self) -> &[T] {
         unsafe { std::slice::from_raw_parts(self.data.as_ref(), self.length) }
@@ -108,3 +235,16 @@ where
         seq.end()
     }
 }
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for SRArray<T>
+where
+    T: SRArrayElement + serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Vec::<T>::deserialize(deserializer).map(SRArray::from)
+    }
+}
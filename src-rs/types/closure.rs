@@ -0,0 +1,177 @@
+use std::{ffi::c_void, marker::PhantomData};
+
+use crate::{
+    swift::{self, SwiftObject},
+    Int, SwiftRet,
+};
+
+use super::SRObject;
+
+type Callback<Args> = Box<dyn FnMut(Args) + Send>;
+
+#[doc(hidden)]
+#[repr(C)]
+pub struct SRClosureImpl {
+    context: *mut c_void,
+    call: unsafe extern "C" fn(*mut c_void, *const *const c_void),
+    destroy: unsafe extern "C" fn(*mut c_void),
+}
+
+/// A Rust closure that can be passed to Swift,
+/// analagous to `SRClosure` in Swift.
+///
+/// `Args` is a tuple of the closure's argument types,
+/// which can be any types that can be returned from Swift.
+/// On the Swift side the closure is called like a regular function,
+/// with arguments whose types must match those in `Args`.
+///
+/// The closure is owned by the Swift object,
+/// so it is dropped once both Rust and Swift are done with it.
+/// Calls are serialised by Swift,
+/// but may happen on any thread so the closure must be [`Send`].
+/// Calling an `SRClosure` from inside its own closure is a fatal error,
+/// as is calling it with the wrong number or size of arguments.
+/// Since Swift can't handle Rust panics, panicking inside the closure aborts the process.
+///
+/// ```rust
+/// use swift_rs::{swift, Int, SRClosure, SRString};
+/// use std::sync::mpsc;
+///
+/// swift!(fn count_to(to: Int, callback: &SRClosure<(SRString, Int)>));
+///
+/// let (tx, rx) = mpsc::channel();
+/// let callback = SRClosure::new(move |name: SRString, n: Int| {
+///     tx.send(format!("{name} {n}")).unwrap();
+/// });
+///
+/// unsafe { count_to(2, &callback) };
+///
+/// assert_eq!(rx.try_iter().collect::<Vec<_>>(), ["count 1", "count 2"]);
+/// ```
+#[repr(transparent)]
+pub struct SRClosure<Args>(SRObject<SRClosureImpl>, PhantomData<fn(Args)>);
+
+impl<Args: SwiftClosureArgs> SRClosure<Args> {
+    /// Creates a closure that Swift can call any number of times.
+    pub fn new(mut callback: impl SwiftClosureFn<Args>) -> Self {
+        Self::from_boxed(Box::new(move |args| callback.call(args)))
+    }
+
+    /// Creates a closure that runs `callback` the first time Swift calls it,
+    /// ignoring any later calls.
+    pub fn once(callback: impl SwiftClosureFnOnce<Args>) -> Self {
+        let mut callback = Some(callback);
+        Self::from_boxed(Box::new(move |args| {
+            if let Some(callback) = callback.take() {
+                callback.call_once(args)
+            }
+        }))
+    }
+
+    fn from_boxed(callback: Callback<Args>) -> Self {
+        unsafe extern "C" fn call<Args: SwiftClosureArgs>(
+            context: *mut c_void,
+            args: *const *const c_void,
+        ) {
+            let callback = &mut *(context as *mut Callback<Args>);
+            callback(Args::from_args(args))
+        }
+
+        unsafe extern "C" fn destroy<Args>(context: *mut c_void) {
+            drop(Box::from_raw(context as *mut Callback<Args>))
+        }
+
+        let context = Box::into_raw(Box::new(callback));
+        let call: unsafe extern "C" fn(_, _) = call::<Args>;
+        let destroy: unsafe extern "C" fn(_) = destroy::<Args>;
+
+        let object = unsafe {
+            swift::closure_new(
+                context as *mut c_void,
+                call as *const c_void,
+                destroy as *const c_void,
+                Args::SIZES.as_ptr(),
+                Args::SIZES.len() as Int,
+            )
+        };
+
+        Self(object, PhantomData)
+    }
+}
+
+impl<Args> SwiftObject for SRClosure<Args> {
+    type Shape = SRClosureImpl;
+
+    fn get_object(&self) -> &SRObject<Self::Shape> {
+        &self.0
+    }
+}
+
+/// Tuples of values that Swift can call an [`SRClosure`] with.
+pub trait SwiftClosureArgs: 'static {
+    /// Reads the arguments from the array of pointers passed by Swift,
+    /// retaining any objects.
+    ///
+    /// # Safety
+    /// `args` must point to values with the same types as `Self`.
+    unsafe fn from_args(args: *const *const c_void) -> Self;
+
+    /// Sizes of the arguments as Swift passes them,
+    /// which Swift checks its arguments against before calling the closure.
+    #[doc(hidden)]
+    const SIZES: &'static [Int];
+}
+
+/// Rust closures that can be called by Swift through an [`SRClosure`] with `Args`.
+pub trait SwiftClosureFn<Args>: Send + 'static {
+    #[doc(hidden)]
+    fn call(&mut self, args: Args);
+}
+
+/// Rust closures that can be called once by Swift through an [`SRClosure`] with `Args`.
+pub trait SwiftClosureFnOnce<Args>: Send + 'static {
+    #[doc(hidden)]
+    fn call_once(self, args: Args);
+}
+
+macro_rules! closure_impl {
+    ($($arg:ident),*) => {
+        #[allow(non_snake_case, unused_variables, unused_mut, unused_assignments, clippy::unused_unit)]
+        impl<$($arg: SwiftRet + 'static),*> SwiftClosureArgs for ($($arg,)*) {
+            const SIZES: &'static [Int] = &[$(std::mem::size_of::<$arg::RetType>() as Int),*];
+
+            unsafe fn from_args(args: *const *const c_void) -> Self {
+                let mut args = args;
+                $(
+                    let $arg = std::ptr::read(*args as *const $arg::RetType);
+                    // Swift only lends its arguments
+                    $arg.retain();
+                    let $arg = $arg::from_ret($arg);
+                    args = args.add(1);
+                )*
+
+                ($($arg,)*)
+            }
+        }
+
+        #[allow(non_snake_case)]
+        impl<F: FnMut($($arg),*) + Send + 'static, $($arg),*> SwiftClosureFn<($($arg,)*)> for F {
+            fn call(&mut self, ($($arg,)*): ($($arg,)*)) {
+                self($($arg),*)
+            }
+        }
+
+        #[allow(non_snake_case)]
+        impl<F: FnOnce($($arg),*) + Send + 'static, $($arg),*> SwiftClosureFnOnce<($($arg,)*)> for F {
+            fn call_once(self, ($($arg,)*): ($($arg,)*)) {
+                self($($arg),*)
+            }
+        }
+    };
+}
+
+closure_impl!();
+closure_impl!(A);
+closure_impl!(A, B);
+closure_impl!(A, B, C);
+closure_impl!(A, B, C, D);
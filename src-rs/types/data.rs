@@ -5,7 +5,12 @@ use crate::{
 
 use super::{array::SRArray, SRObject};
 
-use std::ops::Deref;
+use std::{
+    borrow::Borrow,
+    ffi::c_void,
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
 
 type Data = SRArray<u8>;
 
@@ -23,7 +28,11 @@ type Data = SRArray<u8>;
 /// ```
 /// [_corresponding Swift code_](https://github.com/Brendonovich/swift-rs/blob/07269e511f1afb71e2fcfa89ca5d7338bceb20e8/tests/swift-pkg/doctests.swift#L68)
 #[repr(transparent)]
-pub struct SRData(SRObject<Data>);
+pub struct SRData(pub(crate) SRObject<Data>);
+
+// Swift's Data is immutable once it's been passed to Rust
+unsafe impl Send for SRData {}
+unsafe impl Sync for SRData {}
 
 impl SRData {
     pub fn as_slice(&self) -> &[u8] {
@@ -33,6 +42,39 @@ impl SRData {
     pub fn to_vec(&self) -> Vec<u8> {
         self.as_slice().to_vec()
     }
+
+    /// Creates an `SRData` that uses the `Vec`'s allocation instead of copying it into Swift.
+    /// The `Vec` is dropped once Swift no longer needs it, which may happen on any thread.
+    pub fn from_vec(vec: Vec<u8>) -> Self {
+        Self::from_allocation(vec)
+    }
+
+    /// Creates an `SRData` that uses the boxed slice's allocation instead of copying it into Swift.
+    /// The slice is dropped once Swift no longer needs it, which may happen on any thread.
+    pub fn from_boxed(boxed: Box<[u8]>) -> Self {
+        Self::from_allocation(boxed)
+    }
+
+    fn from_allocation<T: Deref<Target = [u8]> + Send + 'static>(allocation: T) -> Self {
+        unsafe extern "C" fn destroy<T>(context: *mut c_void) {
+            drop(Box::from_raw(context as *mut T))
+        }
+
+        // Vecs and boxed slices keep their bytes on the heap,
+        // so they stay in place when the allocation is moved into the context
+        let (data, size) = (allocation.as_ptr(), allocation.len());
+        let context = Box::into_raw(Box::new(allocation));
+        let destroy: unsafe extern "C" fn(_) = destroy::<T>;
+
+        unsafe {
+            swift::data_from_rust(
+                data,
+                size as Int,
+                context as *mut c_void,
+                destroy as *const c_void,
+            )
+        }
+    }
 }
 
 impl SwiftObject for SRData {
@@ -57,12 +99,66 @@ impl AsRef<[u8]> for SRData {
     }
 }
 
+impl Borrow<[u8]> for SRData {
+    fn borrow(&self) -> &[u8] {
+        self
+    }
+}
+
+// Data is compared by its contents, like `SRData` in Swift
+
+impl PartialEq for SRData {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for SRData {}
+
+impl Hash for SRData {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
 impl From<&[u8]> for SRData {
     fn from(value: &[u8]) -> Self {
         unsafe { swift::data_from_bytes(value.as_ptr(), value.len() as Int) }
     }
 }
 
+impl From<Vec<u8>> for SRData {
+    fn from(value: Vec<u8>) -> Self {
+        Self::from_vec(value)
+    }
+}
+
+impl From<Box<[u8]>> for SRData {
+    fn from(value: Box<[u8]>) -> Self {
+        Self::from_boxed(value)
+    }
+}
+
+#[cfg(feature = "bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+impl SRData {
+    /// Converts the data into [`Bytes`](bytes::Bytes) without copying it.
+    ///
+    /// The `Bytes` and any of its clones keep the Swift object alive,
+    /// and it's released once they've all been dropped.
+    pub fn into_bytes(self) -> bytes::Bytes {
+        bytes::Bytes::from_owner(self)
+    }
+}
+
+#[cfg(feature = "bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+impl From<SRData> for bytes::Bytes {
+    fn from(value: SRData) -> Self {
+        value.into_bytes()
+    }
+}
+
 #[cfg(feature = "serde")]
 impl serde::Serialize for SRData {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -72,3 +168,43 @@ impl serde::Serialize for SRData {
         serializer.serialize_bytes(self)
     }
 }
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SRData {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct DataVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for DataVisitor {
+            type Value = SRData;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("bytes or a sequence of bytes")
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                Ok(SRData::from(v))
+            }
+
+            fn visit_byte_buf<E: serde::de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+                Ok(SRData::from_vec(v))
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                // The hint comes from the input, so it can't be trusted to be reasonable
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(1 << 20));
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+                Ok(SRData::from_vec(bytes))
+            }
+        }
+
+        deserializer.deserialize_byte_buf(DataVisitor)
+    }
+}
@@ -0,0 +1,168 @@
+use std::{
+    borrow::Borrow,
+    collections::{BTreeMap, HashMap},
+    iter::Zip,
+    slice::Iter,
+};
+
+use crate::{
+    swift::{self, SwiftObject},
+    SRArray, SRArrayElement, SRObject, ThreadSafe,
+};
+
+#[doc(hidden)]
+#[repr(C)]
+pub struct SRDictionaryImpl<K, V> {
+    keys: SRArray<K>,
+    values: SRArray<V>,
+}
+
+// Dictionaries are immutable once they've been passed to Rust
+unsafe impl<K, V> ThreadSafe for SRDictionaryImpl<K, V> {}
+
+/// Dictionary type for scalars and objects,
+/// equivalent to `SRDictionary` in Swift.
+///
+/// Keys and values are stored as a pair of [`SRArray`]s,
+/// so looking up a key is a linear search.
+///
+/// ```rust
+/// use swift_rs::{swift, Int, SRDictionary, SRString};
+///
+/// swift!(fn get_scores() -> SRDictionary<SRString, Int>);
+///
+/// let scores = unsafe { get_scores() };
+///
+/// assert_eq!(scores.len(), 2);
+/// assert_eq!(scores.get("Brendan"), Some(&3));
+/// assert_eq!(scores.get("Oscar"), None);
+/// ```
+///
+/// ## Creating from Rust
+///
+/// Dictionaries can be created from a `HashMap` or `BTreeMap`
+/// whose keys and values can be put in an [`SRArray`].
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use swift_rs::{swift, Int, SRDictionary, SRString};
+///
+/// swift!(fn total_score(scores: &SRDictionary<SRString, Int>) -> Int);
+///
+/// let scores: SRDictionary<SRString, Int> =
+///     HashMap::from([("Brendan".into(), 3), ("Oscar".into(), 4)]).into();
+///
+/// assert_eq!(unsafe { total_score(&scores) }, 7);
+/// ```
+#[repr(transparent)]
+pub struct SRDictionary<K, V>(pub(crate) SRObject<SRDictionaryImpl<K, V>>);
+
+impl<K, V> SRDictionary<K, V> {
+    pub fn len(&self) -> usize {
+        self.keys().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn keys(&self) -> &[K] {
+        self.0.keys.as_slice()
+    }
+
+    pub fn values(&self) -> &[V] {
+        self.0.values.as_slice()
+    }
+
+    /// Iterates over the entries of the dictionary, in no particular order.
+    pub fn iter(&self) -> Zip<Iter<'_, K>, Iter<'_, V>> {
+        self.keys().iter().zip(self.values())
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+    {
+        self.iter()
+            .find(|(k, _)| (*k).borrow() == key)
+            .map(|(_, v)| v)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+}
+
+impl<K, V> SwiftObject for SRDictionary<K, V> {
+    type Shape = SRDictionaryImpl<K, V>;
+
+    fn get_object(&self) -> &SRObject<Self::Shape> {
+        &self.0
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a SRDictionary<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Zip<Iter<'a, K>, Iter<'a, V>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: SRArrayElement, V: SRArrayElement> SRDictionary<K, V> {
+    // The entries must already have unique keys
+    fn from_entries(entries: impl Iterator<Item = (K, V)>) -> Self {
+        let (keys, values): (Vec<K>, Vec<V>) = entries.unzip();
+        let keys = SRArray::from(keys).cast();
+        let values = SRArray::from(values).cast();
+
+        let dictionary = unsafe { swift::dictionary_from_arrays(&keys, &values) };
+        SRDictionary(dictionary.cast())
+    }
+}
+
+impl<K: SRArrayElement, V: SRArrayElement, S> From<HashMap<K, V, S>> for SRDictionary<K, V> {
+    fn from(value: HashMap<K, V, S>) -> Self {
+        Self::from_entries(value.into_iter())
+    }
+}
+
+impl<K: SRArrayElement, V: SRArrayElement> From<BTreeMap<K, V>> for SRDictionary<K, V> {
+    fn from(value: BTreeMap<K, V>) -> Self {
+        Self::from_entries(value.into_iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<K, V> serde::Serialize for SRDictionary<K, V>
+where
+    K: serde::Serialize,
+    V: serde::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_map(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, K, V> serde::Deserialize<'de> for SRDictionary<K, V>
+where
+    K: SRArrayElement + Eq + std::hash::Hash + serde::Deserialize<'de>,
+    V: SRArrayElement + serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        HashMap::<K, V>::deserialize(deserializer).map(SRDictionary::from)
+    }
+}
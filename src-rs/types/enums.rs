@@ -0,0 +1,217 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    marker::PhantomData,
+};
+
+use crate::{swift::SwiftObject, *};
+
+/// A Rust enum that can be received from Swift.
+///
+/// This is implemented by `#[derive(SwiftEnum)]` from the `derive` feature,
+/// for both C-like enums and enums with fields.
+///
+/// Swift may have cases that the Rust enum doesn't,
+/// so [`swift!`](crate::swift) functions return enums as `Result<T, UnknownDiscriminant>`.
+/// Returning `T` directly, which panics on an unknown case,
+/// can be opted into with `#[swift_enum(panic_on_unknown)]`.
+///
+/// C-like enums must be `#[repr(isize)]` so that they're passed as their discriminant,
+/// which Swift reads and writes as the `rawValue` of an `Int` enum:
+///
+/// ```ignore
+/// use swift_rs::{swift, SwiftEnum, UnknownDiscriminant};
+///
+/// #[derive(SwiftEnum)]
+/// #[repr(isize)]
+/// enum Direction {
+///     Up,
+///     Down,
+/// }
+///
+/// swift!(fn flip(direction: Direction) -> Result<Direction, UnknownDiscriminant>);
+/// ```
+///
+/// Enums with fields are returned from Swift as an [`SREnum`],
+/// which holds the case's associated values in an object that Rust copies them out of.
+/// The fields can be scalars, optional scalars, objects, optional objects and C-like enums.
+/// Enums with fields must themselves be wrapped in [`SREnum`]:
+///
+/// ```ignore
+/// use swift_rs::{swift, Float64, SRString, SwiftEnum};
+///
+/// #[derive(SwiftEnum)]
+/// #[swift_enum(panic_on_unknown)]
+/// enum Shape {
+///     Circle { radius: Float64 },
+///     Polygon(SRString),
+///     Empty,
+/// }
+///
+/// swift!(fn random_shape() -> Shape);
+/// ```
+///
+/// A C-like enum that's a field of an object is read in place,
+/// so it must be declared as an `Int` and converted with `TryFrom`
+/// if Swift could store a case that Rust doesn't know about.
+///
+/// The Swift side of both kinds of enum can be generated with `SwiftLinker::with_swift_classes`.
+pub trait SwiftEnum: Sized {
+    /// What Swift returns the enum as,
+    /// which is its discriminant for C-like enums and an [`SREnum`] otherwise.
+    #[doc(hidden)]
+    type Repr: SwiftRet;
+
+    /// Converts the enum returned by Swift.
+    /// This function should never be called manually,
+    /// instead you should rely on [`swift!`](crate::swift) to call it for you.
+    ///
+    /// # Safety
+    /// The value must have been returned by Swift and retained.
+    #[doc(hidden)]
+    unsafe fn from_repr(repr: Self::Repr) -> Result<Self, UnknownDiscriminant>;
+
+    /// Converts a case received from Swift,
+    /// returning an error if the tag or one of its fields isn't a known variant.
+    /// This function should never be called manually,
+    /// instead you should rely on [`SREnum`] to call it for you.
+    ///
+    /// # Safety
+    /// The payload must be the object that Swift creates for the tag's case.
+    #[doc(hidden)]
+    unsafe fn from_swift(
+        tag: Int,
+        payload: Option<&SRObject<()>>,
+    ) -> Result<Self, UnknownDiscriminant>;
+}
+
+impl<T: SwiftEnum> SwiftRet for Result<T, UnknownDiscriminant> {
+    type RetType = <T::Repr as SwiftRet>::RetType;
+
+    unsafe fn from_ret(ret: Self::RetType) -> Self {
+        T::from_repr(T::Repr::from_ret(ret))
+    }
+}
+
+#[doc(hidden)]
+#[repr(C)]
+pub struct SREnumImpl {
+    tag: Int,
+    payload: Option<SRObject<()>>,
+}
+
+/// An enum received from Swift, equivalent to `SREnum<T>` in Swift.
+///
+/// Enums with fields are returned from [`swift!`](crate::swift) functions directly,
+/// but need to be wrapped in `SREnum` when they're a field of an object.
+#[repr(transparent)]
+pub struct SREnum<T>(SRObject<SREnumImpl>, PhantomData<T>);
+
+impl<T: SwiftEnum> SREnum<T> {
+    /// Copies the enum out of Swift,
+    /// or returns an error if Swift has a case that `T` doesn't.
+    pub fn get(&self) -> Result<T, UnknownDiscriminant> {
+        unsafe { T::from_swift(self.0.tag, self.0.payload.as_ref()) }
+    }
+}
+
+impl<T> SwiftObject for SREnum<T> {
+    type Shape = SREnumImpl;
+
+    fn get_object(&self) -> &SRObject<Self::Shape> {
+        &self.0
+    }
+}
+
+/// A type that can be a field of an enum's payload.
+///
+/// This is implemented by `#[derive(SwiftEnum)]` for C-like enums,
+/// which are stored as their discriminant and checked when they're copied.
+#[doc(hidden)]
+pub trait SwiftEnumField: Sized {
+    /// The type of the Swift property that the field is stored in.
+    type Repr;
+
+    /// Copies the field out of an object that Swift owns, retaining it if it's an object.
+    ///
+    /// # Safety
+    /// The field must have been read from a Swift property of the same type.
+    unsafe fn copy_field(field: &Self::Repr) -> Result<Self, UnknownDiscriminant>;
+}
+
+macro_rules! field_impl {
+    ($($t:ty),+) => {
+        $(impl SwiftEnumField for $t {
+            type Repr = Self;
+
+            unsafe fn copy_field(field: &Self::Repr) -> Result<Self, UnknownDiscriminant> {
+                Ok(*field)
+            }
+        }
+
+        impl SwiftEnumField for Option<$t> {
+            type Repr = SROptional<$t>;
+
+            unsafe fn copy_field(field: &Self::Repr) -> Result<Self, UnknownDiscriminant> {
+                Ok((*field).into())
+            }
+        })+
+    };
+}
+
+field_impl!(
+    Bool, Int, Int8, Int16, Int32, Int64, UInt, UInt8, UInt16, UInt32, UInt64, Float32, Float64
+);
+
+impl<T: SwiftObject> SwiftEnumField for T {
+    type Repr = Self;
+
+    unsafe fn copy_field(field: &Self::Repr) -> Result<Self, UnknownDiscriminant> {
+        let value = std::ptr::read(field);
+        SwiftObject::retain(&value);
+        Ok(value)
+    }
+}
+
+impl<T: SwiftObject> SwiftEnumField for Option<T> {
+    type Repr = Self;
+
+    unsafe fn copy_field(field: &Self::Repr) -> Result<Self, UnknownDiscriminant> {
+        let value = std::ptr::read(field);
+        if let Some(value) = &value {
+            SwiftObject::retain(value);
+        }
+        Ok(value)
+    }
+}
+
+/// Copies a field of an enum's payload.
+/// This function should never be called manually,
+/// instead you should rely on `#[derive(SwiftEnum)]` to call it for you.
+///
+/// # Safety
+/// See [`SwiftEnumField::copy_field`].
+#[doc(hidden)]
+pub unsafe fn copy_field<T: SwiftEnumField>(field: &T::Repr) -> Result<T, UnknownDiscriminant> {
+    T::copy_field(field)
+}
+
+/// A discriminant from Swift that doesn't match any variant of a Rust enum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownDiscriminant {
+    /// Name of the Rust enum.
+    pub type_name: &'static str,
+    pub discriminant: Int,
+}
+
+impl Display for UnknownDiscriminant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} isn't a discriminant of `{}`",
+            self.discriminant, self.type_name
+        )
+    }
+}
+
+impl Error for UnknownDiscriminant {}
@@ -0,0 +1,120 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+use crate::{swift, SRClosure, SRObject, SwiftRet, ThreadSafe};
+
+#[doc(hidden)]
+#[repr(C)]
+pub struct SRTaskImpl {
+    // Swift's tasks are opaque, so they're only accessed from Swift
+    _private: [u8; 0],
+}
+
+// Swift's tasks can be cancelled from any thread
+unsafe impl ThreadSafe for SRTaskImpl {}
+
+struct State<T> {
+    value: Option<T>,
+    waker: Option<Waker>,
+}
+
+impl<T> State<T> {
+    fn complete(&mut self, value: T) {
+        self.value = Some(value);
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// The result of calling an async Swift function, declared with `swift!(async fn ...)`.
+///
+/// The Swift function is started as soon as it's called and runs in its own `Task`,
+/// so this future doesn't depend on any particular executor.
+/// Dropping the future before it completes cancels the `Task`.
+///
+/// On the Swift side the function receives a completion `SRClosure` as its last argument,
+/// and returns an `SRTask` that runs the async work:
+///
+/// ```swift
+/// @_cdecl("async_echo")
+/// func asyncEcho(string: UnsafeRawPointer, completion: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+///     let string: SRString = fromRust(string)
+///     let completion: SRClosure = fromRust(completion)
+///     return toRust(SRTask(completion) {
+///         try? await Task.sleep(nanoseconds: 1_000_000)
+///         return string
+///     })
+/// }
+/// ```
+///
+/// Async functions that throw can be declared as returning `Result<T, SwiftError>`,
+/// and use `SRTask(completion, catching: ...)`.
+///
+/// The result is sent from the thread the Swift task completes on,
+/// so it must be [`Send`], and the future is [`Send`] as well.
+#[must_use = "futures do nothing unless polled, and dropping this cancels the Swift task"]
+pub struct SwiftFuture<T> {
+    state: Arc<Mutex<State<T>>>,
+    task: Option<SRObject<SRTaskImpl>>,
+}
+
+impl<T: SwiftRet + Send + 'static> SwiftFuture<T> {
+    /// Creates a future along with the completion closure that resolves it.
+    /// This function should never be called manually,
+    /// instead you should rely on the [`swift!`] macro to call it for you.
+    #[doc(hidden)]
+    pub fn new() -> (Self, SRClosure<(T,)>) {
+        let state = Arc::new(Mutex::new(State {
+            value: None,
+            waker: None,
+        }));
+
+        let completion = {
+            let state = state.clone();
+            SRClosure::once(move |value: T| state.lock().unwrap().complete(value))
+        };
+
+        (Self { state, task: None }, completion)
+    }
+
+    #[doc(hidden)]
+    pub fn with_task(mut self, task: SRObject<SRTaskImpl>) -> Self {
+        self.task = Some(task);
+        self
+    }
+}
+
+impl<T> Future for SwiftFuture<T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let value = {
+            let mut state = self.state.lock().unwrap();
+            match state.value.take() {
+                Some(value) => value,
+                None => {
+                    state.waker = Some(cx.waker().clone());
+                    return Poll::Pending;
+                }
+            }
+        };
+
+        // The task has finished, so there's nothing left to cancel
+        self.task = None;
+
+        Poll::Ready(value)
+    }
+}
+
+impl<T> Drop for SwiftFuture<T> {
+    fn drop(&mut self) {
+        if let Some(task) = &self.task {
+            unsafe { swift::task_cancel(task) }
+        }
+    }
+}
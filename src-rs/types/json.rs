@@ -0,0 +1,88 @@
+use std::marker::PhantomData;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{swift::SwiftObject, SRArray, SRData, SRObject};
+
+/// A value that's passed between Rust and Swift as JSON,
+/// instead of as an object with a matching `#[repr(C)]` layout.
+///
+/// Rust serializes the value with serde, and Swift decodes it with `Codable`
+/// using `fromRust(json:)` and `toRust(json:)`.
+/// This is slower than sharing objects directly,
+/// but works for any type and doesn't break when fields are added or reordered.
+///
+/// Swift's `Codable` uses property names as keys, so Rust types will usually need
+/// `#[serde(rename_all = "camelCase")]`.
+///
+/// ```rust
+/// use serde::{Deserialize, Serialize};
+/// use swift_rs::{swift, SwiftJson};
+///
+/// #[derive(Serialize, Deserialize)]
+/// #[serde(rename_all = "camelCase")]
+/// struct Release {
+///     version: String,
+///     download_count: u64,
+/// }
+///
+/// swift!(fn next_release(release: &SwiftJson<Release>) -> SwiftJson<Release>);
+///
+/// let release = SwiftJson::new(&Release {
+///     version: "1.0.8".into(),
+///     download_count: 1000,
+/// })
+/// .unwrap();
+///
+/// let next = unsafe { next_release(&release) }.decode().unwrap();
+///
+/// assert_eq!(next.version, "1.0.9");
+/// assert_eq!(next.download_count, 0);
+/// ```
+#[repr(transparent)]
+pub struct SwiftJson<T> {
+    data: SRData,
+    _value: PhantomData<T>,
+}
+
+impl<T: Serialize> SwiftJson<T> {
+    /// Serializes `value` so that it can be passed to Swift.
+    pub fn new(value: &T) -> Result<Self, serde_json::Error> {
+        let json = serde_json::to_vec(value)?;
+        Ok(Self::from_data(SRData::from_vec(json)))
+    }
+}
+
+impl<T: DeserializeOwned> SwiftJson<T> {
+    /// Deserializes the value that Swift encoded.
+    pub fn decode(&self) -> Result<T, serde_json::Error> {
+        serde_json::from_slice(&self.data)
+    }
+}
+
+impl<T> SwiftJson<T> {
+    /// Treats `data` as a JSON encoded `T`.
+    pub fn from_data(data: SRData) -> Self {
+        Self {
+            data,
+            _value: PhantomData,
+        }
+    }
+
+    /// The encoded JSON.
+    pub fn as_data(&self) -> &SRData {
+        &self.data
+    }
+
+    pub fn into_data(self) -> SRData {
+        self.data
+    }
+}
+
+impl<T> SwiftObject for SwiftJson<T> {
+    type Shape = SRArray<u8>;
+
+    fn get_object(&self) -> &SRObject<Self::Shape> {
+        self.data.get_object()
+    }
+}
@@ -1,11 +1,34 @@
 mod array;
+mod closure;
 mod data;
+mod dictionary;
+mod enums;
+mod future;
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+mod json;
+mod mutable;
 mod object;
+mod optional;
+mod result;
 mod scalars;
+mod set;
 mod string;
+mod weak;
 
 pub use array::*;
+pub use closure::*;
 pub use data::*;
+pub use dictionary::*;
+pub use enums::*;
+pub use future::*;
+#[cfg(feature = "json")]
+pub use json::*;
+pub use mutable::*;
 pub use object::*;
+pub use optional::*;
+pub use result::*;
 pub use scalars::*;
+pub use set::*;
 pub use string::*;
+pub use weak::*;
@@ -0,0 +1,315 @@
+use std::{
+    io,
+    marker::PhantomData,
+    mem,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+};
+
+use crate::{swift, *};
+
+#[doc(hidden)]
+#[repr(C)]
+pub struct SRMutableBufferImpl {
+    pointer: NonNull<u8>,
+    length: usize,
+    capacity: usize,
+}
+
+/// Growable array of scalars that's written to from Rust,
+/// without copying it when it's passed to Swift.
+///
+/// The elements are stored in a buffer allocated by Swift that only the `SRMutableArray` can access,
+/// which is converted into an [`SRArray`] with [`into_array`](SRMutableArray::into_array)
+/// so that it can be passed to Swift.
+///
+/// ```rust
+/// use swift_rs::{swift, Float64, SRArray, SRMutableArray};
+///
+/// swift!(fn average(samples: &SRArray<Float64>) -> Float64);
+///
+/// let mut samples = SRMutableArray::new();
+/// samples.push(1.0);
+/// samples.extend_from_slice(&[2.0, 6.0]);
+///
+/// assert_eq!(unsafe { average(&samples.into_array()) }, 3.0);
+/// ```
+pub struct SRMutableArray<T> {
+    buffer: SRObject<SRMutableBufferImpl>,
+    _elements: PhantomData<T>,
+}
+
+// Nothing else can access the buffer
+unsafe impl<T: Send> Send for SRMutableArray<T> {}
+unsafe impl<T: Sync> Sync for SRMutableArray<T> {}
+
+impl<T: SRMutableArrayElement> SRMutableArray<T> {
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        let buffer = unsafe {
+            swift::mutable_buffer_new(
+                mem::size_of::<T>() as Int,
+                mem::align_of::<T>() as Int,
+                capacity as Int,
+            )
+        };
+
+        Self {
+            buffer,
+            _elements: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.buffer.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity
+    }
+
+    /// Reserves space for at least `additional` more elements,
+    /// which may move the elements to a new buffer.
+    pub fn reserve(&mut self, additional: usize) {
+        if self.capacity() - self.len() < additional {
+            unsafe { swift::mutable_buffer_reserve(&self.buffer, additional as Int) }
+        }
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.extend_from_slice(&[value]);
+    }
+
+    pub fn extend_from_slice(&mut self, slice: &[T]) {
+        self.reserve(slice.len());
+
+        let buffer = unsafe { self.buffer.get_mut() };
+        unsafe {
+            let end = buffer.pointer.as_ptr().cast::<T>().add(buffer.length);
+            end.copy_from_nonoverlapping(slice.as_ptr(), slice.len());
+        }
+        buffer.length += slice.len();
+    }
+
+    /// Shortens the array to `len` elements,
+    /// or does nothing if it's already shorter.
+    pub fn truncate(&mut self, len: usize) {
+        let buffer = unsafe { self.buffer.get_mut() };
+        buffer.length = buffer.length.min(len);
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { std::slice::from_raw_parts(self.buffer.pointer.as_ptr().cast(), self.len()) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        let length = self.len();
+        let buffer = unsafe { self.buffer.get_mut() };
+        unsafe { std::slice::from_raw_parts_mut(buffer.pointer.as_ptr().cast(), length) }
+    }
+
+    /// Converts the array into an immutable [`SRArray`] that can be passed to Swift,
+    /// without copying the elements.
+    pub fn into_array(self) -> SRArray<T> {
+        T::array_from_buffer(&self.buffer)
+    }
+}
+
+impl<T: SRMutableArrayElement> Default for SRMutableArray<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: SRMutableArrayElement> Deref for SRMutableArray<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T: SRMutableArrayElement> DerefMut for SRMutableArray<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl<T: SRMutableArrayElement> Extend<T> for SRMutableArray<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T: SRMutableArrayElement> From<SRMutableArray<T>> for SRArray<T> {
+    fn from(value: SRMutableArray<T>) -> Self {
+        value.into_array()
+    }
+}
+
+impl io::Write for SRMutableArray<u8> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Types that [`SRMutableArray`]s can hold.
+///
+/// Only scalars can be written from Rust,
+/// since Swift needs to know their type to read the array.
+pub trait SRMutableArrayElement: Copy {
+    #[doc(hidden)]
+    fn array_from_buffer(buffer: &SRObject<SRMutableBufferImpl>) -> SRArray<Self>;
+}
+
+macro_rules! scalar_impl {
+    ($($t:ty => $fn:ident),+) => {
+        $(impl SRMutableArrayElement for $t {
+            fn array_from_buffer(buffer: &SRObject<SRMutableBufferImpl>) -> SRArray<Self> {
+                unsafe { swift::$fn(buffer) }
+            }
+        })+
+    };
+}
+
+scalar_impl!(
+    Bool => array_from_bool_buffer,
+    Int => array_from_int_buffer,
+    Int8 => array_from_int8_buffer,
+    Int16 => array_from_int16_buffer,
+    Int32 => array_from_int32_buffer,
+    Int64 => array_from_int64_buffer,
+    UInt => array_from_uint_buffer,
+    UInt8 => array_from_uint8_buffer,
+    UInt16 => array_from_uint16_buffer,
+    UInt32 => array_from_uint32_buffer,
+    UInt64 => array_from_uint64_buffer,
+    Float32 => array_from_float32_buffer,
+    Float64 => array_from_float64_buffer
+);
+
+/// Growable byte buffer that's written to from Rust,
+/// without copying it when it's passed to Swift.
+///
+/// This is to [`SRData`] what [`SRMutableArray`] is to [`SRArray`],
+/// and implements [`io::Write`] so that encoders can write into it directly.
+///
+/// ```rust
+/// use std::io::Write;
+/// use swift_rs::{swift, SRData, SRMutableData};
+///
+/// swift!(fn echo_data(data: &SRData) -> SRData);
+///
+/// let mut data = SRMutableData::new();
+/// write!(data, "hello {}", 42).unwrap();
+///
+/// let data = unsafe { echo_data(&data.into_data()) };
+///
+/// assert_eq!(data.as_slice(), b"hello 42");
+/// ```
+#[derive(Default)]
+pub struct SRMutableData(SRMutableArray<u8>);
+
+impl SRMutableData {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(SRMutableArray::with_capacity(capacity))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        self.0.reserve(additional)
+    }
+
+    pub fn push(&mut self, byte: u8) {
+        self.0.push(byte)
+    }
+
+    pub fn extend_from_slice(&mut self, slice: &[u8]) {
+        self.0.extend_from_slice(slice)
+    }
+
+    pub fn truncate(&mut self, len: usize) {
+        self.0.truncate(len)
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        self.0.as_mut_slice()
+    }
+
+    /// Converts the buffer into an immutable [`SRData`] that can be passed to Swift,
+    /// without copying the bytes.
+    pub fn into_data(self) -> SRData {
+        unsafe { swift::data_from_mutable_buffer(&self.0.buffer) }
+    }
+}
+
+impl Deref for SRMutableData {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl DerefMut for SRMutableData {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl Extend<u8> for SRMutableData {
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        self.0.extend(iter)
+    }
+}
+
+impl From<SRMutableData> for SRData {
+    fn from(value: SRMutableData) -> Self {
+        value.into_data()
+    }
+}
+
+impl io::Write for SRMutableData {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
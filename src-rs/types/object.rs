@@ -1,10 +1,19 @@
-use crate::swift::{self, SwiftObject};
+use crate::{
+    swift::{self, SwiftObject},
+    Int, SRArray, SRData, SRDictionary, SRSet, SRString,
+};
 use std::{ffi::c_void, ops::Deref, ptr::NonNull};
 
 #[doc(hidden)]
 #[repr(C)]
 pub struct SRObjectImpl<T> {
+    // Objective-C's NSObject only holds an isa pointer
+    #[cfg(target_vendor = "apple")]
     _nsobject_offset: u8,
+    // swift-corelibs-foundation's NSObject is a native Swift class,
+    // which has a metadata pointer and a refcount
+    #[cfg(not(target_vendor = "apple"))]
+    _nsobject_offset: [usize; 2],
     data: T,
 }
 
@@ -33,6 +42,218 @@ pub struct SRObjectImpl<T> {
 #[repr(transparent)]
 pub struct SRObject<T>(pub(crate) NonNull<SRObjectImpl<T>>);
 
+impl<T> SRObject<T> {
+    /// Offset of `T` from the start of the Swift object.
+    #[doc(hidden)]
+    pub const DATA_OFFSET: usize = std::mem::offset_of!(SRObjectImpl<T>, data);
+
+    /// Reinterprets the object as having a different shape,
+    /// transferring ownership of its retain.
+    pub(crate) fn cast<U>(self) -> SRObject<U> {
+        let ptr = self.0.cast();
+        std::mem::forget(self);
+        SRObject(ptr)
+    }
+
+    /// Mutable access to the object's fields.
+    ///
+    /// # Safety
+    /// Nothing else, including Swift, may access the object while the reference is alive.
+    pub(crate) unsafe fn get_mut(&mut self) -> &mut T {
+        &mut (*self.0.as_ptr()).data
+    }
+
+    /// Returns `true` if both `SRObject`s point to the same Swift object.
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        this.0 == other.0
+    }
+
+    /// Consumes the `SRObject`, returning a pointer to the Swift object
+    /// that still owns its retain.
+    ///
+    /// The object must be passed back to [`SRObject::from_raw`] to release it,
+    /// such as after passing it through a C callback's `void*` context.
+    pub fn into_raw(self) -> *mut c_void {
+        let ptr = self.0.as_ptr() as *mut c_void;
+        std::mem::forget(self);
+        ptr
+    }
+
+    /// Takes ownership of a retain on a Swift object,
+    /// such as a pointer from [`SRObject::into_raw`] or from `Unmanaged.passRetained(...)`.
+    ///
+    /// # Safety
+    /// `ptr` must be a non-null pointer to an `NSObject` with the same shape as `T`,
+    /// whose retain isn't owned by anything else.
+    pub unsafe fn from_raw(ptr: *mut c_void) -> Self {
+        Self(NonNull::new_unchecked(ptr.cast()))
+    }
+
+    /// Retains a Swift object that's owned by something else,
+    /// such as a pointer from `Unmanaged.passUnretained(...)`.
+    ///
+    /// # Safety
+    /// `ptr` must be a non-null pointer to an `NSObject` with the same shape as `T`.
+    pub unsafe fn from_raw_retained(ptr: *mut c_void) -> Self {
+        swift::retain_object(ptr);
+        Self::from_raw(ptr)
+    }
+
+    /// The object's current retain count.
+    ///
+    /// This is only useful for debugging,
+    /// since it includes retains held by autorelease pools and by Swift while it's reading the count.
+    pub fn retain_count(&self) -> Int {
+        unsafe { swift::retain_count(self.0.as_ptr() as *const c_void) }
+    }
+}
+
+/// Types whose Swift class can be created from Rust,
+/// by passing a Rust value's fields to a Swift function that creates the object.
+///
+/// This allows objects to be created with [`SRObject::new`],
+/// and with the `serde` feature to be deserialized as long as `Self` can be deserialized.
+///
+/// ```rust
+/// use swift_rs::{swift, Int, SRObject, SRString, SwiftConstructor};
+///
+/// #[repr(C)]
+/// struct Config {
+///     name: SRString,
+///     retries: Int,
+/// }
+///
+/// swift!(fn config_new(name: &SRString, retries: Int) -> SRObject<Config>);
+///
+/// impl SwiftConstructor for Config {
+///     fn construct(self) -> SRObject<Self> {
+///         unsafe { config_new(&self.name, self.retries) }
+///     }
+/// }
+///
+/// let config = SRObject::new(Config {
+///     name: "main".into(),
+///     retries: 3,
+/// });
+///
+/// assert_eq!(config.name.as_str(), "main");
+/// ```
+pub trait SwiftConstructor: Sized {
+    /// Creates the Swift object from this value's fields.
+    fn construct(self) -> SRObject<Self>;
+}
+
+impl<T: SwiftConstructor> SRObject<T> {
+    /// Creates a Swift object with the same fields as `value`.
+    pub fn new(value: T) -> Self {
+        value.construct()
+    }
+}
+
+impl<T: SwiftConstructor> From<T> for SRObject<T> {
+    fn from(value: T) -> Self {
+        value.construct()
+    }
+}
+
+/// Marks a Swift object as safe to use from any thread,
+/// which allows `SRObject<T>` to be [`Send`] and [`Sync`] as long as `T` is [`Sync`].
+///
+/// Objects are otherwise bound to the thread they were received on,
+/// which is the safe default for classes that can be mutated by Swift
+/// or that must be used from the main thread.
+///
+/// ```rust
+/// use swift_rs::{Int, SRObject, SRString, ThreadSafe};
+///
+/// #[repr(C)]
+/// struct Config {
+///     name: SRString,
+///     retries: Int,
+/// }
+///
+/// // The Swift class only has `let` properties
+/// unsafe impl ThreadSafe for Config {}
+///
+/// fn assert_send<T: Send + Sync>() {}
+/// assert_send::<SRObject<Config>>();
+/// ```
+///
+/// # Safety
+/// The Swift class must be safe to read, retain and release from any thread,
+/// such as by being immutable or `Sendable`.
+pub unsafe trait ThreadSafe {}
+
+unsafe impl<T: ThreadSafe + Sync> Send for SRObject<T> {}
+unsafe impl<T: ThreadSafe + Sync> Sync for SRObject<T> {}
+
+impl<T> Clone for SRObject<T> {
+    /// Retains the object, returning another reference to it.
+    fn clone(&self) -> Self {
+        unsafe { swift::retain_object(self.0.as_ptr() as *const c_void) };
+        Self(self.0)
+    }
+}
+
+// Types that wrap an SRObject have the same reference counting methods
+macro_rules! wrapper_impl {
+    ($($t:ident $(<$($gen:ident),+>)? => |$object:ident| $wrap:expr),+) => {
+        $(impl$(<$($gen),+>)? $t$(<$($gen),+>)? {
+            /// Returns `true` if both point to the same Swift object.
+            pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+                SRObject::ptr_eq(this.get_object(), other.get_object())
+            }
+
+            /// See [`SRObject::into_raw`].
+            pub fn into_raw(self) -> *mut c_void {
+                let ptr = self.get_object().0.as_ptr() as *mut c_void;
+                std::mem::forget(self);
+                ptr
+            }
+
+            /// See [`SRObject::from_raw`].
+            ///
+            /// # Safety
+            /// `ptr` must be a non-null pointer to an object of this type,
+            /// whose retain isn't owned by anything else.
+            pub unsafe fn from_raw(ptr: *mut c_void) -> Self {
+                let $object = SRObject::from_raw(ptr);
+                $wrap
+            }
+
+            /// See [`SRObject::from_raw_retained`].
+            ///
+            /// # Safety
+            /// `ptr` must be a non-null pointer to an object of this type.
+            pub unsafe fn from_raw_retained(ptr: *mut c_void) -> Self {
+                let $object = SRObject::from_raw_retained(ptr);
+                $wrap
+            }
+
+            /// See [`SRObject::retain_count`].
+            pub fn retain_count(&self) -> Int {
+                self.get_object().retain_count()
+            }
+        }
+
+        impl$(<$($gen),+>)? Clone for $t$(<$($gen),+>)? {
+            /// Retains the object, returning another reference to it.
+            fn clone(&self) -> Self {
+                let $object = self.get_object().clone();
+                $wrap
+            }
+        })+
+    };
+}
+
+wrapper_impl!(
+    SRArray<T> => |object| SRArray(object),
+    SRData => |object| SRData(object),
+    SRString => |object| SRString(SRData(object)),
+    SRDictionary<K, V> => |object| SRDictionary(object),
+    SRSet<T> => |object| SRSet(object)
+);
+
 impl<T> SwiftObject for SRObject<T> {
     type Shape = T;
 
@@ -73,3 +294,16 @@ where
         self.deref().serialize(serializer)
     }
 }
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for SRObject<T>
+where
+    T: SwiftConstructor + serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        T::deserialize(deserializer).map(T::construct)
+    }
+}
@@ -0,0 +1,27 @@
+use crate::*;
+
+/// C representation of an optional scalar,
+/// which [`swift!`] uses to pass `Option<T>` to and from Swift.
+/// In Swift this is `SROptionalInt`, `SROptionalDouble` and so on.
+#[doc(hidden)]
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct SROptional<T> {
+    has_value: Bool,
+    value: T,
+}
+
+impl<T: Copy + Default> From<Option<T>> for SROptional<T> {
+    fn from(value: Option<T>) -> Self {
+        Self {
+            has_value: value.is_some(),
+            value: value.unwrap_or_default(),
+        }
+    }
+}
+
+impl<T> From<SROptional<T>> for Option<T> {
+    fn from(value: SROptional<T>) -> Self {
+        value.has_value.then_some(value.value)
+    }
+}
@@ -0,0 +1,111 @@
+use std::{
+    error::Error,
+    ffi::c_void,
+    fmt::{Display, Formatter},
+    ptr::NonNull,
+};
+
+use crate::{swift::SwiftObject, Int, SRObject, SRString, UInt64};
+
+#[doc(hidden)]
+#[repr(C)]
+pub struct SRErrorImpl {
+    type_name: SRString,
+    description: SRString,
+    domain: Option<SRString>,
+    code: Int,
+}
+
+#[doc(hidden)]
+#[repr(C)]
+pub struct SRResultImpl {
+    value: Option<NonNull<c_void>>,
+    error: Option<SRObject<SRErrorImpl>>,
+    scalar: [UInt64; 2],
+}
+
+impl SRResultImpl {
+    /// Space for scalars, which is enough for the largest `SROptional`.
+    pub(crate) const SCALAR_SIZE: usize = std::mem::size_of::<[UInt64; 2]>();
+}
+
+impl SRObject<SRResultImpl> {
+    fn error(&self) -> Option<SwiftError> {
+        self.error.as_ref().map(|error| SwiftError {
+            type_name: error.type_name.to_string(),
+            description: error.description.to_string(),
+            domain: error.domain.as_ref().map(ToString::to_string),
+            code: error.domain.is_some().then_some(error.code),
+        })
+    }
+
+    /// Converts an `SRResult` into a [`Result`],
+    /// retaining the value if there is one.
+    ///
+    /// # Safety
+    /// The successful value must be either `nil` or an object of type `T`.
+    pub(crate) unsafe fn into_result<T: SwiftObject>(self) -> Result<Option<T>, SwiftError> {
+        if let Some(error) = self.error() {
+            return Err(error);
+        }
+
+        Ok(self.value.map(|value| {
+            // SwiftObjects are all pointers to the object
+            let value = std::ptr::read(&value as *const NonNull<c_void> as *const T);
+            value.retain();
+            value
+        }))
+    }
+
+    /// Converts an `SRResult` holding a scalar into a [`Result`].
+    ///
+    /// # Safety
+    /// The successful value must have been created from a scalar with the same layout as `T`,
+    /// which must fit in [`SRResultImpl::SCALAR_SIZE`] bytes.
+    pub(crate) unsafe fn into_scalar_result<T: Copy>(self) -> Result<T, SwiftError> {
+        debug_assert!(std::mem::size_of::<T>() <= SRResultImpl::SCALAR_SIZE);
+
+        match self.error() {
+            Some(error) => Err(error),
+            None => Ok(std::ptr::read(self.scalar.as_ptr() as *const T)),
+        }
+    }
+}
+
+/// An error thrown by a Swift function.
+///
+/// Swift functions that throw can be called with [`swift!`](crate::swift) by returning
+/// an `SRResult`, which is declared as returning `Result<T, SwiftError>` in Rust,
+/// where `T` is an object, a scalar, an `Option` of either, or `()`.
+///
+/// ```rust
+/// use swift_rs::{swift, SRString, SwiftError};
+///
+/// swift!(fn validate_name(name: &SRString) -> Result<SRString, SwiftError>);
+///
+/// let name = unsafe { validate_name(&"Brendan".into()) }.unwrap();
+/// assert_eq!(name.as_str(), "Brendan");
+///
+/// let error = unsafe { validate_name(&"".into()) }.err().unwrap();
+/// assert_eq!(error.type_name, "test_swift.ValidationError");
+/// assert_eq!(error.description, "Name can't be empty");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwiftError {
+    /// Fully qualified name of the error's type, such as `MyModule.MyError`.
+    pub type_name: String,
+    /// The error's `localizedDescription`.
+    pub description: String,
+    /// The error's `NSError` domain, if it could be bridged to an `NSError`.
+    pub domain: Option<String>,
+    /// The error's `NSError` code, if it could be bridged to an `NSError`.
+    pub code: Option<Int>,
+}
+
+impl Display for SwiftError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.description)
+    }
+}
+
+impl Error for SwiftError {}
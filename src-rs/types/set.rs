@@ -0,0 +1,133 @@
+use std::{
+    borrow::Borrow,
+    collections::{BTreeSet, HashSet},
+    slice::Iter,
+};
+
+use crate::{
+    swift::{self, SwiftObject},
+    SRArray, SRArrayElement, SRObject, ThreadSafe,
+};
+
+#[doc(hidden)]
+#[repr(C)]
+pub struct SRSetImpl<T> {
+    elements: SRArray<T>,
+}
+
+// Sets are immutable once they've been passed to Rust
+unsafe impl<T> ThreadSafe for SRSetImpl<T> {}
+
+/// Set type for scalars and objects,
+/// equivalent to `SRSet` in Swift.
+///
+/// Elements are stored in an [`SRArray`],
+/// so checking if the set contains a value is a linear search.
+///
+/// ```rust
+/// use std::collections::HashSet;
+/// use swift_rs::{swift, SRSet, SRString};
+///
+/// swift!(fn add_tag(tags: &SRSet<SRString>, tag: &SRString) -> SRSet<SRString>);
+///
+/// let tags: SRSet<SRString> = HashSet::from(["swift".into()]).into();
+/// let tags = unsafe { add_tag(&tags, &"rust".into()) };
+///
+/// assert_eq!(tags.len(), 2);
+/// assert!(tags.contains("swift"));
+/// assert!(tags.contains("rust"));
+/// ```
+#[repr(transparent)]
+pub struct SRSet<T>(pub(crate) SRObject<SRSetImpl<T>>);
+
+impl<T> SRSet<T> {
+    pub fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The elements of the set, in no particular order.
+    pub fn as_slice(&self) -> &[T] {
+        self.0.elements.as_slice()
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.as_slice().iter()
+    }
+
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+    {
+        self.iter().any(|element| element.borrow() == value)
+    }
+}
+
+impl<T> SwiftObject for SRSet<T> {
+    type Shape = SRSetImpl<T>;
+
+    fn get_object(&self) -> &SRObject<Self::Shape> {
+        &self.0
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SRSet<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: SRArrayElement> SRSet<T> {
+    // The elements must already be unique
+    fn from_elements(elements: impl Iterator<Item = T>) -> Self {
+        let elements = elements.collect::<SRArray<T>>().cast();
+
+        let set = unsafe { swift::set_from_array(&elements) };
+        SRSet(set.cast())
+    }
+}
+
+impl<T: SRArrayElement, S> From<HashSet<T, S>> for SRSet<T> {
+    fn from(value: HashSet<T, S>) -> Self {
+        Self::from_elements(value.into_iter())
+    }
+}
+
+impl<T: SRArrayElement> From<BTreeSet<T>> for SRSet<T> {
+    fn from(value: BTreeSet<T>) -> Self {
+        Self::from_elements(value.into_iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for SRSet<T>
+where
+    T: serde::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for SRSet<T>
+where
+    T: SRArrayElement + Eq + std::hash::Hash + serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        HashSet::<T>::deserialize(deserializer).map(SRSet::from)
+    }
+}
@@ -1,5 +1,7 @@
 use std::{
+    borrow::Borrow,
     fmt::{Display, Error, Formatter},
+    hash::{Hash, Hasher},
     ops::Deref,
 };
 
@@ -21,7 +23,7 @@ use crate::{
 /// ```
 /// [_corresponding Swift code_](https://github.com/Brendonovich/swift-rs/blob/07269e511f1afb71e2fcfa89ca5d7338bceb20e8/tests/swift-pkg/doctests.swift#L56)
 #[repr(transparent)]
-pub struct SRString(SRData);
+pub struct SRString(pub(crate) SRData);
 
 impl SRString {
     pub fn as_str(&self) -> &str {
@@ -51,6 +53,28 @@ impl AsRef<[u8]> for SRString {
     }
 }
 
+impl Borrow<str> for SRString {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+// Strings are compared by their contents, like `SRString` in Swift
+
+impl PartialEq for SRString {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for SRString {}
+
+impl Hash for SRString {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
 impl From<&str> for SRString {
     fn from(string: &str) -> Self {
         unsafe { swift::string_from_bytes(string.as_ptr(), string.len() as Int) }
@@ -63,6 +87,33 @@ impl Display for SRString {
     }
 }
 
+#[cfg(feature = "bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+impl SRString {
+    /// Converts the string's UTF-8 bytes into [`Bytes`](bytes::Bytes) without copying them.
+    ///
+    /// See [`SRData::into_bytes`].
+    pub fn into_bytes(self) -> bytes::Bytes {
+        self.0.into_bytes()
+    }
+
+    /// Converts the string into a [`ByteString`](bytestring::ByteString) without copying it.
+    ///
+    /// See [`SRData::into_bytes`].
+    pub fn into_byte_string(self) -> bytestring::ByteString {
+        // Swift strings are always valid UTF-8
+        unsafe { bytestring::ByteString::from_bytes_unchecked(self.into_bytes()) }
+    }
+}
+
+#[cfg(feature = "bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+impl From<SRString> for bytestring::ByteString {
+    fn from(value: SRString) -> Self {
+        value.into_byte_string()
+    }
+}
+
 #[cfg(feature = "serde")]
 impl serde::Serialize for SRString {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
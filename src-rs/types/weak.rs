@@ -0,0 +1,80 @@
+use std::marker::PhantomData;
+
+use crate::{
+    swift::{self, SwiftObject},
+    SRObject, ThreadSafe,
+};
+
+#[doc(hidden)]
+#[repr(C)]
+pub struct SRWeakImpl {
+    // Swift's weak references are opaque, so they're only accessed from Swift
+    _private: [u8; 0],
+}
+
+// Swift's weak references are atomic
+unsafe impl ThreadSafe for SRWeakImpl {}
+
+/// A weak reference to an `NSObject`, equivalent to `SRWeak` in Swift.
+///
+/// Unlike [`SRObject`] this doesn't keep the object alive,
+/// so it can be used to break retain cycles between Rust and Swift.
+/// It's created with [`SRObject::downgrade`], and the object can be accessed with
+/// [`upgrade`](SRWeak::upgrade) for as long as something else is keeping it alive.
+///
+/// ```rust
+/// use swift_rs::{autoreleasepool, swift, Bool, Int, SRObject};
+///
+/// #[repr(C)]
+/// struct CustomObject {
+///     a: Int,
+///     b: Bool
+/// }
+///
+/// swift!(fn get_custom_object() -> SRObject<CustomObject>);
+///
+/// // Swift autoreleases the objects it returns,
+/// // so this makes sure that they aren't kept alive by the pool afterwards
+/// let weak = autoreleasepool!({
+///     let object = unsafe { get_custom_object() };
+///     let weak = object.downgrade();
+///
+///     assert_eq!(weak.upgrade().unwrap().a, 3);
+///     weak
+/// });
+///
+/// assert!(weak.upgrade().is_none());
+/// ```
+///
+/// `SRWeak<T>` can only be sent to other threads if `SRObject<T>` can,
+/// since upgrading it gives another reference to the object.
+///
+/// `SRWeak` can also be a field of an object,
+/// as long as the Swift class stores an `SRWeak` rather than a `weak var`.
+#[repr(transparent)]
+pub struct SRWeak<T>(SRObject<SRWeakImpl>, PhantomData<SRObject<T>>);
+
+impl<T> SRWeak<T> {
+    /// Gets a strong reference to the object,
+    /// or `None` if it has already been deallocated.
+    pub fn upgrade(&self) -> Option<SRObject<T>> {
+        unsafe { swift::weak_upgrade(&self.0) }.map(SRObject::cast)
+    }
+}
+
+impl<T> SRObject<T> {
+    /// Creates a weak reference to this object.
+    pub fn downgrade(&self) -> SRWeak<T> {
+        // Every SRObject is a pointer to an NSObject
+        let object = unsafe { &*(self as *const Self as *const SRObject<()>) };
+        SRWeak(unsafe { swift::weak_new(object) }, PhantomData)
+    }
+}
+
+impl<T> SwiftObject for SRWeak<T> {
+    type Shape = SRWeakImpl;
+
+    fn get_object(&self) -> &SRObject<Self::Shape> {
+        &self.0
+    }
+}
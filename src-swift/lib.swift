@@ -1,4 +1,5 @@
 import Foundation
+@_exported import SwiftRsC
 
 public class SRArray<T>: NSObject {
     // Used by Rust
@@ -7,21 +8,85 @@ public class SRArray<T>: NSObject {
     
     // Actual array, deallocates objects inside automatically
     let array: [T];
+    // Owns the elements instead of `array` when they were written by Rust
+    let owner: AnyObject?
 
     public override init() {
         self.array = [];
         self.pointer = UnsafePointer(self.array);
         self.length = 0;
+        self.owner = nil
     }
 
     public init(_ data: [T]) {
         self.array = data;
         self.pointer = UnsafePointer(self.array)
         self.length = data.count
+        self.owner = nil
+    }
+
+    init(pointer: UnsafePointer<T>, length: Int, owner: AnyObject) {
+        self.array = []
+        self.pointer = pointer
+        self.length = length
+        self.owner = owner
+    }
+
+    convenience init(_ buffer: SRMutableBuffer) {
+        let pointer = buffer.pointer.assumingMemoryBound(to: T.self)
+        self.init(pointer: UnsafePointer(pointer), length: buffer.length, owner: buffer)
+    }
+
+    /// The elements, without copying them into a Swift array.
+    public var elements: UnsafeBufferPointer<T> {
+        return UnsafeBufferPointer(start: self.pointer, count: self.length)
     }
 
     public func toArray() -> [T] {
-        return Array(self.array)
+        if self.owner == nil {
+            return Array(self.array)
+        }
+        return Array(self.elements)
+    }
+}
+
+/// A growable buffer that Rust writes elements into directly,
+/// received in Rust as `SRMutableData` or `SRMutableArray<T>`.
+/// Rust converts it into an `SRData` or `SRArray` before passing it to Swift.
+public class SRMutableBuffer: NSObject {
+    // Used by Rust
+    var pointer: UnsafeMutableRawPointer
+    var length: Int
+    var capacity: Int
+
+    let stride: Int
+    let alignment: Int
+
+    init(stride: Int, alignment: Int, capacity: Int) {
+        self.pointer = UnsafeMutableRawPointer.allocate(byteCount: stride * capacity, alignment: alignment)
+        self.length = 0
+        self.capacity = capacity
+        self.stride = stride
+        self.alignment = alignment
+    }
+
+    func reserve(_ additional: Int) {
+        let required = self.length + additional
+        if required <= self.capacity {
+            return
+        }
+
+        let capacity = max(required, self.capacity * 2)
+        let pointer = UnsafeMutableRawPointer.allocate(byteCount: self.stride * capacity, alignment: self.alignment)
+        pointer.copyMemory(from: self.pointer, byteCount: self.stride * self.length)
+        self.pointer.deallocate()
+
+        self.pointer = pointer
+        self.capacity = capacity
+    }
+
+    deinit {
+        self.pointer.deallocate()
     }
 }
 
@@ -31,6 +96,25 @@ public class SRObjectArray: NSObject {
     public init(_ data: [NSObject]) {
         self.data = SRArray(data)
     }
+
+    public func toArray() -> [NSObject] {
+        return self.data.toArray()
+    }
+}
+
+/// Memory owned by Rust, which Rust deallocates once Swift no longer needs it.
+class SRRustAllocation: NSObject {
+    let context: UnsafeMutableRawPointer
+    let destroy: @convention(c) (UnsafeMutableRawPointer) -> Void
+
+    init(context: UnsafeMutableRawPointer, destroy: @escaping @convention(c) (UnsafeMutableRawPointer) -> Void) {
+        self.context = context
+        self.destroy = destroy
+    }
+
+    deinit {
+        destroy(context)
+    }
 }
 
 public class SRData: NSObject {
@@ -51,6 +135,21 @@ public class SRData: NSObject {
     public func toArray() -> [UInt8] {
         return self.data.toArray()
     }
+
+    // Compared by contents so that data and strings can be dictionary keys and set elements
+
+    public override func isEqual(_ object: Any?) -> Bool {
+        guard let other = object as? SRData, type(of: other) == type(of: self) else {
+            return false
+        }
+        return self.data.elements.elementsEqual(other.data.elements)
+    }
+
+    public override var hash: Int {
+        var hasher = Hasher()
+        hasher.combine(bytes: UnsafeRawBufferPointer(self.data.elements))
+        return hasher.finalize()
+    }
 }
 
 public class SRString: SRData {
@@ -67,8 +166,484 @@ public class SRString: SRData {
     }
 
     public func toString() -> String {
-        return String(bytes: self.data.array, encoding: .utf8)!
+        return String(bytes: self.data.elements, encoding: .utf8)!
+    }
+}
+
+/// Reads the elements of an `SRArray` that Rust may have created.
+func arrayElements<T>(_ array: NSObject) -> [T] {
+    if let array = array as? SRArray<T> {
+        return array.toArray()
+    }
+    // Rust creates arrays of custom objects as SRArray<NSObject>
+    return (array as! SRArray<NSObject>).toArray().map { $0 as! T }
+}
+
+/// A dictionary that Rust receives as `SRDictionary<K, V>`.
+/// Keys and values should be scalars or `NSObject`s such as `SRString`.
+public class SRDictionary: NSObject {
+    // Used by Rust, both are SRArrays
+    let keys: NSObject
+    let values: NSObject
+
+    public init<K: Hashable, V>(_ dictionary: [K: V]) {
+        let entries = Array(dictionary)
+        self.keys = SRArray(entries.map { $0.key })
+        self.values = SRArray(entries.map { $0.value })
+    }
+
+    init(keys: NSObject, values: NSObject) {
+        self.keys = keys
+        self.values = values
+    }
+
+    public func toDictionary<K: Hashable, V>() -> [K: V] {
+        let keys: [K] = arrayElements(self.keys)
+        let values: [V] = arrayElements(self.values)
+        return Dictionary(zip(keys, values), uniquingKeysWith: { _, last in last })
+    }
+}
+
+/// A set that Rust receives as `SRSet<T>`.
+/// Elements should be scalars or `NSObject`s such as `SRString`.
+public class SRSet: NSObject {
+    // Used by Rust, an SRArray
+    let elements: NSObject
+
+    public init<T: Hashable>(_ set: Set<T>) {
+        self.elements = SRArray(Array(set))
+    }
+
+    init(elements: NSObject) {
+        self.elements = elements
+    }
+
+    public func toSet<T: Hashable>() -> Set<T> {
+        return Set(arrayElements(self.elements) as [T])
+    }
+}
+
+/// A weak reference to an object, which Rust receives as `SRWeak<T>`.
+public class SRWeak: NSObject {
+    public weak var object: AnyObject?
+
+    public init(_ object: AnyObject) {
+        self.object = object
+    }
+
+    public func get<T: AnyObject>(as type: T.Type = T.self) -> T? {
+        return self.object as? T
+    }
+}
+
+/// An enum with associated values that Rust can receive.
+/// Conformances are generated by `SwiftLinker::with_swift_classes` for the enums it finds.
+public protocol SREnumRepresentable {
+    /// Index of the case.
+    var rustTag: Int { get }
+    /// An object holding the case's associated values, or `nil` if it doesn't have any.
+    var rustPayload: NSObject? { get }
+}
+
+/// An enum that Rust receives as `SREnum<T>`, or directly as `T` from a `@_cdecl` function.
+public class SREnum<T: SREnumRepresentable>: NSObject {
+    // Used by Rust
+    let tag: Int
+    let payload: NSObject?
+
+    public let value: T
+
+    public init(_ value: T) {
+        self.tag = value.rustTag
+        self.payload = value.rustPayload
+        self.value = value
+    }
+}
+
+public class SRError: NSObject {
+    // Used by Rust
+    let typeName: SRString
+    let errorDescription: SRString
+    let domain: SRString?
+    let code: Int
+
+    public init(_ error: Error) {
+        self.typeName = SRString(String(reflecting: type(of: error)))
+        self.errorDescription = SRString(error.localizedDescription)
+
+        #if _runtime(_ObjC)
+        let nsError = error as NSError
+        self.domain = SRString(nsError.domain)
+        self.code = nsError.code
+        #else
+        if let nsError = error as? NSError {
+            self.domain = SRString(nsError.domain)
+            self.code = nsError.code
+        } else if let customError = error as? CustomNSError {
+            self.domain = SRString(type(of: customError).errorDomain)
+            self.code = customError.errorCode
+        } else {
+            self.domain = nil
+            self.code = 0
+        }
+        #endif
+    }
+}
+
+/// The result of a throwing function, which Rust receives as `Result<T, SwiftError>`.
+public class SRResult: NSObject {
+    // Used by Rust
+    let value: NSObject?
+    let error: SRError?
+    // Scalar values, stored with the same layout as in Rust
+    let scalar: (UInt64, UInt64)
+
+    private init(value: NSObject?, error: SRError?, scalar: (UInt64, UInt64) = (0, 0)) {
+        self.value = value
+        self.error = error
+        self.scalar = scalar
+    }
+
+    /// A successful result. `value` should be `nil` if Rust expects `Result<(), SwiftError>`
+    /// or `Result<Option<T>, SwiftError>`.
+    public convenience init(_ value: NSObject?) {
+        self.init(value: value, error: nil)
+    }
+
+    /// A successful scalar result, for Rust functions returning `Result<Int, SwiftError>` and so on.
+    public convenience init<T: SRScalar>(_ value: T) {
+        self.init(value: nil, error: nil, scalar: SRResult.bits(of: value))
+    }
+
+    /// A successful optional scalar result, for Rust functions returning `Result<Option<Int>, SwiftError>` and so on.
+    public convenience init<T: SRScalar>(_ value: T?) {
+        let optional: T.RustOptional = toRust(value)
+        self.init(value: nil, error: nil, scalar: SRResult.bits(of: optional))
+    }
+
+    public convenience init(error: Error) {
+        self.init(value: nil, error: SRError(error))
+    }
+
+    /// Runs `body`, catching any error it throws.
+    public convenience init(catching body: () throws -> NSObject?) {
+        do {
+            self.init(try body())
+        } catch {
+            self.init(error: error)
+        }
+    }
+
+    /// Runs `body`, catching any error it throws.
+    public convenience init<T: SRScalar>(catching body: () throws -> T) {
+        do {
+            self.init(try body())
+        } catch {
+            self.init(error: error)
+        }
+    }
+
+    /// Runs `body`, catching any error it throws.
+    public convenience init<T: SRScalar>(catching body: () throws -> T?) {
+        do {
+            self.init(try body())
+        } catch {
+            self.init(error: error)
+        }
+    }
+
+    private static func bits<T>(of value: T) -> (UInt64, UInt64) {
+        var bits: (UInt64, UInt64) = (0, 0)
+        precondition(MemoryLayout<T>.size <= MemoryLayout.size(ofValue: bits))
+        withUnsafeMutableBytes(of: &bits) { $0.storeBytes(of: value, as: T.self) }
+        return bits
+    }
+}
+
+public class SRClosure: NSObject {
+    // Used by Rust
+    let context: UnsafeMutableRawPointer
+    let call: @convention(c) (UnsafeMutableRawPointer, UnsafePointer<UnsafeRawPointer>?) -> Void
+    let destroy: @convention(c) (UnsafeMutableRawPointer) -> Void
+
+    // Sizes of the Rust closure's arguments, which every call is checked against
+    let argumentSizes: [Int]
+
+    // Rust closures are FnMut, so they can't be called concurrently or from inside themselves.
+    // The lock is recursive so that calls from inside the closure fail the check below
+    // instead of deadlocking.
+    let lock = NSRecursiveLock()
+    var isCalling = false
+
+    init(
+        context: UnsafeMutableRawPointer,
+        call: @escaping @convention(c) (UnsafeMutableRawPointer, UnsafePointer<UnsafeRawPointer>?) -> Void,
+        destroy: @escaping @convention(c) (UnsafeMutableRawPointer) -> Void,
+        argumentSizes: [Int]
+    ) {
+        self.context = context
+        self.call = call
+        self.destroy = destroy
+        self.argumentSizes = argumentSizes
+    }
+
+    deinit {
+        destroy(context)
+    }
+
+    // Arguments must have the same types as the Rust closure's arguments.
+    // Calls with the wrong number of arguments or with arguments of the wrong size
+    // are stopped before they reach Rust.
+
+    public func callAsFunction() {
+        invoke([], sizes: [])
+    }
+
+    public func callAsFunction<A>(_ a: A) {
+        withUnsafePointer(to: a) { a in
+            invoke([UnsafeRawPointer(a)], sizes: [MemoryLayout<A>.size])
+        }
+    }
+
+    public func callAsFunction<A, B>(_ a: A, _ b: B) {
+        withUnsafePointer(to: a) { a in
+            withUnsafePointer(to: b) { b in
+                invoke(
+                    [UnsafeRawPointer(a), UnsafeRawPointer(b)],
+                    sizes: [MemoryLayout<A>.size, MemoryLayout<B>.size]
+                )
+            }
+        }
+    }
+
+    public func callAsFunction<A, B, C>(_ a: A, _ b: B, _ c: C) {
+        withUnsafePointer(to: a) { a in
+            withUnsafePointer(to: b) { b in
+                withUnsafePointer(to: c) { c in
+                    invoke(
+                        [UnsafeRawPointer(a), UnsafeRawPointer(b), UnsafeRawPointer(c)],
+                        sizes: [MemoryLayout<A>.size, MemoryLayout<B>.size, MemoryLayout<C>.size]
+                    )
+                }
+            }
+        }
+    }
+
+    public func callAsFunction<A, B, C, D>(_ a: A, _ b: B, _ c: C, _ d: D) {
+        withUnsafePointer(to: a) { a in
+            withUnsafePointer(to: b) { b in
+                withUnsafePointer(to: c) { c in
+                    withUnsafePointer(to: d) { d in
+                        invoke(
+                            [
+                                UnsafeRawPointer(a), UnsafeRawPointer(b),
+                                UnsafeRawPointer(c), UnsafeRawPointer(d),
+                            ],
+                            sizes: [
+                                MemoryLayout<A>.size, MemoryLayout<B>.size,
+                                MemoryLayout<C>.size, MemoryLayout<D>.size,
+                            ]
+                        )
+                    }
+                }
+            }
+        }
+    }
+
+    private func invoke(_ args: [UnsafeRawPointer], sizes: [Int]) {
+        precondition(
+            sizes.count == argumentSizes.count,
+            "SRClosure called with \(sizes.count) arguments, but the Rust closure takes \(argumentSizes.count)"
+        )
+        precondition(
+            sizes == argumentSizes,
+            "SRClosure called with arguments of sizes \(sizes), but the Rust closure's arguments have sizes \(argumentSizes)"
+        )
+
+        lock.lock()
+        defer { lock.unlock() }
+
+        precondition(!isCalling, "SRClosure can't be called from inside the Rust closure it wraps")
+        isCalling = true
+        defer { isCalling = false }
+
+        args.withUnsafeBufferPointer { args in
+            call(context, args.baseAddress)
+        }
+    }
+}
+
+#if compiler(>=5.5.2) && canImport(_Concurrency)
+/// A running async function, which Rust receives as a `SwiftFuture`.
+///
+/// Dropping the future in Rust cancels the task.
+@available(macOS 10.15, iOS 13.0, *)
+public class SRTask: NSObject {
+    private var task: Task<Void, Never>?
+
+    /// Runs `body` in a new `Task`, calling `completion` with its result.
+    public init<T>(_ completion: SRClosure, _ body: @escaping () async -> T) {
+        super.init()
+        task = Task {
+            completion(await body())
+        }
+    }
+
+    /// Runs `body` in a new `Task`, calling `completion` with an `SRResult`.
+    /// `body` should return `nil` if Rust expects `Result<(), SwiftError>`.
+    public init(_ completion: SRClosure, catching body: @escaping () async throws -> NSObject?) {
+        super.init()
+        task = Task {
+            do {
+                completion(SRResult(try await body()))
+            } catch {
+                completion(SRResult(error: error))
+            }
+        }
+    }
+
+    /// Runs `body` in a new `Task`, calling `completion` with an `SRResult` holding a scalar.
+    public init<T: SRScalar>(_ completion: SRClosure, catching body: @escaping () async throws -> T) {
+        super.init()
+        task = Task {
+            do {
+                completion(SRResult(try await body()))
+            } catch {
+                completion(SRResult(error: error))
+            }
+        }
+    }
+
+    /// Runs `body` in a new `Task`, calling `completion` with an `SRResult` holding an optional scalar.
+    public init<T: SRScalar>(_ completion: SRClosure, catching body: @escaping () async throws -> T?) {
+        super.init()
+        task = Task {
+            do {
+                completion(SRResult(try await body()))
+            } catch {
+                completion(SRResult(error: error))
+            }
+        }
+    }
+
+    public func cancel() {
+        task?.cancel()
+    }
+}
+
+@available(macOS 10.15, iOS 13.0, *)
+@_cdecl("task_cancel")
+func taskCancel(task: UnsafeRawPointer) {
+    let task: SRTask = fromRust(task)
+    task.cancel()
+}
+#endif
+
+/// Converts an object into a pointer that can be returned to Rust from a `@_cdecl` function.
+///
+/// Where there's an Objective-C runtime the object is autoreleased, exactly as if it
+/// had been returned directly. Elsewhere ownership of one retain is passed to Rust.
+public func toRust(_ object: AnyObject) -> UnsafeMutableRawPointer {
+    #if _runtime(_ObjC)
+    return Unmanaged.passRetained(object).autorelease().toOpaque()
+    #else
+    return Unmanaged.passRetained(object).toOpaque()
+    #endif
+}
+
+public func toRust(_ object: AnyObject?) -> UnsafeMutableRawPointer? {
+    return object.map { toRust($0) }
+}
+
+/// Converts an enum into a pointer that can be returned to Rust from a `@_cdecl` function.
+public func toRust<T: SREnumRepresentable>(_ value: T) -> UnsafeMutableRawPointer {
+    return toRust(SREnum(value))
+}
+
+/// Borrows an object that Rust passed to a `@_cdecl` function.
+public func fromRust<T: AnyObject>(_ ptr: UnsafeRawPointer, as type: T.Type = T.self) -> T {
+    return Unmanaged<T>.fromOpaque(ptr).takeUnretainedValue()
+}
+
+public func fromRust<T: AnyObject>(_ ptr: UnsafeRawPointer?, as type: T.Type = T.self) -> T? {
+    return ptr.map { fromRust($0, as: type) }
+}
+
+/// Decodes a value that Rust passed as `SwiftJson<T>`.
+public func fromRust<T: Decodable>(json ptr: UnsafeRawPointer, as type: T.Type = T.self) throws -> T {
+    let data: SRData = fromRust(ptr)
+    return try withExtendedLifetime(data) {
+        let elements = data.data.elements
+        let bytes = Data(
+            bytesNoCopy: UnsafeMutableRawPointer(mutating: elements.baseAddress!),
+            count: elements.count,
+            deallocator: .none
+        )
+        return try JSONDecoder().decode(type, from: bytes)
+    }
+}
+
+/// Encodes a value that Rust receives as `SwiftJson<T>`.
+public func toRust<T: Encodable>(json value: T) throws -> UnsafeMutableRawPointer {
+    let bytes = try JSONEncoder().encode(value)
+    return toRust(SRData(Array(bytes)))
+}
+
+/// An optional scalar that Rust passes as `Option<T>`, such as `SROptionalInt` for `Option<Int>`.
+public protocol SROptional {
+    associatedtype Wrapped
+
+    init()
+    init(has_value: Bool, value: Wrapped)
+    var has_value: Bool { get }
+    var value: Wrapped { get }
+}
+
+extension SROptionalBool: SROptional {}
+extension SROptionalInt: SROptional {}
+extension SROptionalInt8: SROptional {}
+extension SROptionalInt16: SROptional {}
+extension SROptionalInt32: SROptional {}
+extension SROptionalInt64: SROptional {}
+extension SROptionalUInt: SROptional {}
+extension SROptionalUInt8: SROptional {}
+extension SROptionalUInt16: SROptional {}
+extension SROptionalUInt32: SROptional {}
+extension SROptionalUInt64: SROptional {}
+extension SROptionalFloat: SROptional {}
+extension SROptionalDouble: SROptional {}
+
+/// A scalar that can be returned to Rust in an `SRResult`,
+/// along with the type that Rust receives its optional as.
+public protocol SRScalar {
+    associatedtype RustOptional: SROptional where RustOptional.Wrapped == Self
+}
+
+extension Bool: SRScalar { public typealias RustOptional = SROptionalBool }
+extension Int: SRScalar { public typealias RustOptional = SROptionalInt }
+extension Int8: SRScalar { public typealias RustOptional = SROptionalInt8 }
+extension Int16: SRScalar { public typealias RustOptional = SROptionalInt16 }
+extension Int32: SRScalar { public typealias RustOptional = SROptionalInt32 }
+extension Int64: SRScalar { public typealias RustOptional = SROptionalInt64 }
+extension UInt: SRScalar { public typealias RustOptional = SROptionalUInt }
+extension UInt8: SRScalar { public typealias RustOptional = SROptionalUInt8 }
+extension UInt16: SRScalar { public typealias RustOptional = SROptionalUInt16 }
+extension UInt32: SRScalar { public typealias RustOptional = SROptionalUInt32 }
+extension UInt64: SRScalar { public typealias RustOptional = SROptionalUInt64 }
+extension Float: SRScalar { public typealias RustOptional = SROptionalFloat }
+extension Double: SRScalar { public typealias RustOptional = SROptionalDouble }
+
+/// Converts an optional scalar into the type returned to Rust from a `@_cdecl` function.
+public func toRust<T: SROptional>(_ value: T.Wrapped?) -> T {
+    guard let value = value else {
+        return T()
     }
+    return T(has_value: true, value: value)
+}
+
+/// Reads an optional scalar that Rust passed to a `@_cdecl` function.
+public func fromRust<T: SROptional>(_ value: T) -> T.Wrapped? {
+    return value.has_value ? value.value : nil
 }
 
 @_cdecl("retain_object")
@@ -81,14 +656,252 @@ func releaseObject(ptr: UnsafeMutableRawPointer) {
     let _ = Unmanaged<AnyObject>.fromOpaque(ptr).release()
 }
 
+@_cdecl("retain_count")
+func retainCount(ptr: UnsafeMutableRawPointer) -> Int {
+    let object = Unmanaged<AnyObject>.fromOpaque(ptr).takeUnretainedValue()
+    #if _runtime(_ObjC)
+        return CFGetRetainCount(object)
+    #else
+        return Int(_getRetainCount(object))
+    #endif
+}
+
+@_cdecl("is_main_thread")
+func isMainThread() -> Bool {
+    return Thread.isMainThread
+}
+
 @_cdecl("data_from_bytes")
-func dataFromBytes(data: UnsafePointer<UInt8>, size: Int) -> SRData {
+func dataFromBytes(data: UnsafePointer<UInt8>, size: Int) -> UnsafeMutableRawPointer {
     let buffer = UnsafeBufferPointer(start: data, count: size)
-    return SRData(Array(buffer))
+    return toRust(SRData(Array(buffer)))
+}
+
+@_cdecl("data_from_rust")
+func dataFromRust(
+    data: UnsafePointer<UInt8>,
+    size: Int,
+    context: UnsafeMutableRawPointer,
+    destroy: @escaping @convention(c) (UnsafeMutableRawPointer) -> Void
+) -> UnsafeMutableRawPointer {
+    let owner = SRRustAllocation(context: context, destroy: destroy)
+    return toRust(SRData(SRArray(pointer: data, length: size, owner: owner)))
 }
 
 @_cdecl("string_from_bytes")
-func stringFromBytes(data: UnsafePointer<UInt8>, size: Int) -> SRString {
-    let data = dataFromBytes(data: data, size: size);
-    return SRString(data)
+func stringFromBytes(data: UnsafePointer<UInt8>, size: Int) -> UnsafeMutableRawPointer {
+    let buffer = UnsafeBufferPointer(start: data, count: size)
+    return toRust(SRString(SRData(Array(buffer))))
+}
+
+func arrayFromBuffer<T>(_ data: UnsafePointer<T>, _ size: Int) -> UnsafeMutableRawPointer {
+    let buffer = UnsafeBufferPointer(start: data, count: size)
+    return toRust(SRArray(Array(buffer)))
+}
+
+@_cdecl("array_from_bool")
+func arrayFromBool(data: UnsafePointer<Bool>, size: Int) -> UnsafeMutableRawPointer {
+    return arrayFromBuffer(data, size)
+}
+
+@_cdecl("array_from_int")
+func arrayFromInt(data: UnsafePointer<Int>, size: Int) -> UnsafeMutableRawPointer {
+    return arrayFromBuffer(data, size)
+}
+
+@_cdecl("array_from_int8")
+func arrayFromInt8(data: UnsafePointer<Int8>, size: Int) -> UnsafeMutableRawPointer {
+    return arrayFromBuffer(data, size)
+}
+
+@_cdecl("array_from_int16")
+func arrayFromInt16(data: UnsafePointer<Int16>, size: Int) -> UnsafeMutableRawPointer {
+    return arrayFromBuffer(data, size)
+}
+
+@_cdecl("array_from_int32")
+func arrayFromInt32(data: UnsafePointer<Int32>, size: Int) -> UnsafeMutableRawPointer {
+    return arrayFromBuffer(data, size)
+}
+
+@_cdecl("array_from_int64")
+func arrayFromInt64(data: UnsafePointer<Int64>, size: Int) -> UnsafeMutableRawPointer {
+    return arrayFromBuffer(data, size)
+}
+
+@_cdecl("array_from_uint")
+func arrayFromUInt(data: UnsafePointer<UInt>, size: Int) -> UnsafeMutableRawPointer {
+    return arrayFromBuffer(data, size)
+}
+
+@_cdecl("array_from_uint8")
+func arrayFromUInt8(data: UnsafePointer<UInt8>, size: Int) -> UnsafeMutableRawPointer {
+    return arrayFromBuffer(data, size)
+}
+
+@_cdecl("array_from_uint16")
+func arrayFromUInt16(data: UnsafePointer<UInt16>, size: Int) -> UnsafeMutableRawPointer {
+    return arrayFromBuffer(data, size)
+}
+
+@_cdecl("array_from_uint32")
+func arrayFromUInt32(data: UnsafePointer<UInt32>, size: Int) -> UnsafeMutableRawPointer {
+    return arrayFromBuffer(data, size)
+}
+
+@_cdecl("array_from_uint64")
+func arrayFromUInt64(data: UnsafePointer<UInt64>, size: Int) -> UnsafeMutableRawPointer {
+    return arrayFromBuffer(data, size)
+}
+
+@_cdecl("array_from_float32")
+func arrayFromFloat32(data: UnsafePointer<Float32>, size: Int) -> UnsafeMutableRawPointer {
+    return arrayFromBuffer(data, size)
+}
+
+@_cdecl("array_from_float64")
+func arrayFromFloat64(data: UnsafePointer<Float64>, size: Int) -> UnsafeMutableRawPointer {
+    return arrayFromBuffer(data, size)
+}
+
+@_cdecl("mutable_buffer_new")
+func mutableBufferNew(stride: Int, alignment: Int, capacity: Int) -> UnsafeMutableRawPointer {
+    return toRust(SRMutableBuffer(stride: stride, alignment: alignment, capacity: capacity))
+}
+
+@_cdecl("mutable_buffer_reserve")
+func mutableBufferReserve(buffer: UnsafeRawPointer, additional: Int) {
+    let buffer: SRMutableBuffer = fromRust(buffer)
+    buffer.reserve(additional)
+}
+
+@_cdecl("data_from_mutable_buffer")
+func dataFromMutableBuffer(buffer: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    return toRust(SRData(SRArray<UInt8>(fromRust(buffer, as: SRMutableBuffer.self))))
+}
+
+func arrayFromMutableBuffer<T>(_ buffer: UnsafeRawPointer, as type: T.Type) -> UnsafeMutableRawPointer {
+    return toRust(SRArray<T>(fromRust(buffer, as: SRMutableBuffer.self)))
+}
+
+@_cdecl("array_from_bool_buffer")
+func arrayFromBoolBuffer(buffer: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    return arrayFromMutableBuffer(buffer, as: Bool.self)
+}
+
+@_cdecl("array_from_int_buffer")
+func arrayFromIntBuffer(buffer: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    return arrayFromMutableBuffer(buffer, as: Int.self)
+}
+
+@_cdecl("array_from_int8_buffer")
+func arrayFromInt8Buffer(buffer: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    return arrayFromMutableBuffer(buffer, as: Int8.self)
+}
+
+@_cdecl("array_from_int16_buffer")
+func arrayFromInt16Buffer(buffer: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    return arrayFromMutableBuffer(buffer, as: Int16.self)
+}
+
+@_cdecl("array_from_int32_buffer")
+func arrayFromInt32Buffer(buffer: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    return arrayFromMutableBuffer(buffer, as: Int32.self)
+}
+
+@_cdecl("array_from_int64_buffer")
+func arrayFromInt64Buffer(buffer: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    return arrayFromMutableBuffer(buffer, as: Int64.self)
+}
+
+@_cdecl("array_from_uint_buffer")
+func arrayFromUIntBuffer(buffer: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    return arrayFromMutableBuffer(buffer, as: UInt.self)
+}
+
+@_cdecl("array_from_uint8_buffer")
+func arrayFromUInt8Buffer(buffer: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    return arrayFromMutableBuffer(buffer, as: UInt8.self)
+}
+
+@_cdecl("array_from_uint16_buffer")
+func arrayFromUInt16Buffer(buffer: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    return arrayFromMutableBuffer(buffer, as: UInt16.self)
+}
+
+@_cdecl("array_from_uint32_buffer")
+func arrayFromUInt32Buffer(buffer: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    return arrayFromMutableBuffer(buffer, as: UInt32.self)
+}
+
+@_cdecl("array_from_uint64_buffer")
+func arrayFromUInt64Buffer(buffer: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    return arrayFromMutableBuffer(buffer, as: UInt64.self)
+}
+
+@_cdecl("array_from_float32_buffer")
+func arrayFromFloat32Buffer(buffer: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    return arrayFromMutableBuffer(buffer, as: Float32.self)
+}
+
+@_cdecl("array_from_float64_buffer")
+func arrayFromFloat64Buffer(buffer: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    return arrayFromMutableBuffer(buffer, as: Float64.self)
+}
+
+func objectsFromBuffer<T: AnyObject>(_ data: UnsafePointer<UnsafeRawPointer>, _ size: Int) -> [T] {
+    return UnsafeBufferPointer(start: data, count: size).map { fromRust($0) }
+}
+
+@_cdecl("array_from_strings")
+func arrayFromStrings(data: UnsafePointer<UnsafeRawPointer>, size: Int) -> UnsafeMutableRawPointer {
+    return toRust(SRArray<SRString>(objectsFromBuffer(data, size)))
+}
+
+@_cdecl("array_from_data")
+func arrayFromData(data: UnsafePointer<UnsafeRawPointer>, size: Int) -> UnsafeMutableRawPointer {
+    return toRust(SRArray<SRData>(objectsFromBuffer(data, size)))
+}
+
+@_cdecl("array_from_objects")
+func arrayFromObjects(data: UnsafePointer<UnsafeRawPointer>, size: Int) -> UnsafeMutableRawPointer {
+    return toRust(SRArray<NSObject>(objectsFromBuffer(data, size)))
+}
+
+@_cdecl("object_array_from_objects")
+func objectArrayFromObjects(data: UnsafePointer<UnsafeRawPointer>, size: Int) -> UnsafeMutableRawPointer {
+    return toRust(SRObjectArray(objectsFromBuffer(data, size)))
+}
+
+@_cdecl("dictionary_from_arrays")
+func dictionaryFromArrays(keys: UnsafeRawPointer, values: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    return toRust(SRDictionary(keys: fromRust(keys, as: NSObject.self), values: fromRust(values, as: NSObject.self)))
+}
+
+@_cdecl("set_from_array")
+func setFromArray(elements: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    return toRust(SRSet(elements: fromRust(elements, as: NSObject.self)))
+}
+
+@_cdecl("weak_new")
+func weakNew(object: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    return toRust(SRWeak(fromRust(object, as: AnyObject.self)))
+}
+
+@_cdecl("weak_upgrade")
+func weakUpgrade(weak: UnsafeRawPointer) -> UnsafeMutableRawPointer? {
+    let weak: SRWeak = fromRust(weak)
+    return toRust(weak.object)
+}
+
+@_cdecl("closure_new")
+func closureNew(
+    context: UnsafeMutableRawPointer,
+    call: @escaping @convention(c) (UnsafeMutableRawPointer, UnsafePointer<UnsafeRawPointer>?) -> Void,
+    destroy: @escaping @convention(c) (UnsafeMutableRawPointer) -> Void,
+    argumentSizes: UnsafePointer<Int>,
+    argumentCount: Int
+) -> UnsafeMutableRawPointer {
+    let argumentSizes = Array(UnsafeBufferPointer(start: argumentSizes, count: argumentCount))
+    return toRust(SRClosure(context: context, call: call, destroy: destroy, argumentSizes: argumentSizes))
 }
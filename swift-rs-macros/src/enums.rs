@@ -0,0 +1,263 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DataEnum, DeriveInput, Error, Fields, Result};
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream> {
+    let ident = &input.ident;
+
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new_spanned(
+            ident,
+            "SwiftEnum can only be derived for enums",
+        ));
+    };
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "SwiftEnum can't be derived for generic enums",
+        ));
+    }
+    if data.variants.is_empty() {
+        return Err(Error::new_spanned(
+            ident,
+            "SwiftEnum can't be derived for enums without variants",
+        ));
+    }
+
+    let c_like = data
+        .variants
+        .iter()
+        .all(|variant| matches!(variant.fields, Fields::Unit));
+
+    let panic_on_unknown = panic_on_unknown(&input)?;
+    let enum_impl = if c_like {
+        c_like_enum(&input, data)?
+    } else {
+        fields_enum(&input, data)?
+    };
+
+    // Returning the enum itself has to panic when Swift has a case that Rust doesn't,
+    // so it's only implemented when asked for
+    let ret_impl = panic_on_unknown.then(|| {
+        quote! {
+            impl ::swift_rs::SwiftRet for #ident {
+                type RetType = <<Self as ::swift_rs::SwiftEnum>::Repr as ::swift_rs::SwiftRet>::RetType;
+
+                unsafe fn from_ret(ret: Self::RetType) -> Self {
+                    <::core::result::Result<Self, ::swift_rs::UnknownDiscriminant>
+                        as ::swift_rs::SwiftRet>::from_ret(ret)
+                        .unwrap_or_else(|error| ::core::panic!("{}", error))
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        #enum_impl
+        #ret_impl
+    })
+}
+
+/// C-like enums are passed as their discriminant,
+/// which is the `rawValue` of an `Int` enum in Swift.
+fn c_like_enum(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream> {
+    let ident = &input.ident;
+
+    if !is_repr_isize(input)? {
+        return Err(Error::new_spanned(
+            ident,
+            "SwiftEnum requires C-like enums to be #[repr(isize)], \
+             so that they have the same representation as Swift's `Int`",
+        ));
+    }
+
+    let variants = data
+        .variants
+        .iter()
+        .map(|variant| &variant.ident)
+        .collect::<Vec<_>>();
+
+    Ok(quote! {
+        impl ::core::convert::TryFrom<::swift_rs::Int> for #ident {
+            type Error = ::swift_rs::UnknownDiscriminant;
+
+            fn try_from(discriminant: ::swift_rs::Int) -> ::core::result::Result<Self, Self::Error> {
+                #(if discriminant == Self::#variants as ::swift_rs::Int {
+                    return ::core::result::Result::Ok(Self::#variants);
+                })*
+
+                ::core::result::Result::Err(::swift_rs::UnknownDiscriminant {
+                    type_name: ::core::any::type_name::<Self>(),
+                    discriminant,
+                })
+            }
+        }
+
+        impl ::swift_rs::SwiftEnum for #ident {
+            type Repr = ::swift_rs::Int;
+
+            unsafe fn from_repr(
+                repr: Self::Repr,
+            ) -> ::core::result::Result<Self, ::swift_rs::UnknownDiscriminant> {
+                <Self as ::core::convert::TryFrom<::swift_rs::Int>>::try_from(repr)
+            }
+
+            unsafe fn from_swift(
+                tag: ::swift_rs::Int,
+                _payload: ::core::option::Option<&::swift_rs::SRObject<()>>,
+            ) -> ::core::result::Result<Self, ::swift_rs::UnknownDiscriminant> {
+                <Self as ::core::convert::TryFrom<::swift_rs::Int>>::try_from(tag)
+            }
+        }
+
+        impl ::swift_rs::SwiftEnumField for #ident {
+            type Repr = ::swift_rs::Int;
+
+            unsafe fn copy_field(
+                field: &Self::Repr,
+            ) -> ::core::result::Result<Self, ::swift_rs::UnknownDiscriminant> {
+                <Self as ::core::convert::TryFrom<::swift_rs::Int>>::try_from(*field)
+            }
+        }
+
+        impl<'swift_rs_arg> ::swift_rs::SwiftArg<'swift_rs_arg> for #ident {
+            type ArgType = ::swift_rs::Int;
+
+            unsafe fn as_arg(&'swift_rs_arg self) -> Self::ArgType {
+                match self {
+                    #(Self::#variants => Self::#variants as ::swift_rs::Int,)*
+                }
+            }
+        }
+    })
+}
+
+/// Enums with fields are returned as an `SREnum`,
+/// whose tag is the index of the variant and whose payload is an object
+/// with a stored property for each of the variant's fields.
+/// The payload struct declares each field as the type Swift stores it as,
+/// so that C-like enums are read as their discriminant and checked before they're converted.
+fn fields_enum(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream> {
+    let ident = &input.ident;
+
+    let mut payloads = vec![];
+    let mut arms = vec![];
+
+    for (tag, variant) in data.variants.iter().enumerate() {
+        let variant_ident = &variant.ident;
+        let tag = tag as isize;
+
+        if let Some((_, discriminant)) = &variant.discriminant {
+            return Err(Error::new_spanned(
+                discriminant,
+                "SwiftEnum doesn't support discriminants on enums with fields",
+            ));
+        }
+
+        let value = match &variant.fields {
+            Fields::Unit => quote!(Self::#variant_ident),
+            fields => {
+                let payload = format_ident!("{ident}{variant_ident}Payload");
+                let names = fields
+                    .iter()
+                    .enumerate()
+                    .map(|(i, field)| match &field.ident {
+                        Some(ident) => ident.clone(),
+                        None => format_ident!("_{i}"),
+                    })
+                    .collect::<Vec<_>>();
+                let types = fields.iter().map(|field| &field.ty).collect::<Vec<_>>();
+
+                payloads.push(quote! {
+                    #[allow(dead_code)]
+                    #[repr(C)]
+                    struct #payload {
+                        #(#names: <#types as ::swift_rs::SwiftEnumField>::Repr,)*
+                    }
+                });
+
+                let values = names
+                    .iter()
+                    .zip(&types)
+                    .map(|(name, ty)| quote!(::swift_rs::copy_field::<#ty>(&payload.#name)?));
+                let value = match fields {
+                    Fields::Named(_) => quote!(Self::#variant_ident { #(#names: #values),* }),
+                    _ => quote!(Self::#variant_ident(#(#values),*)),
+                };
+
+                quote! {{
+                    let payload = &**(payload.ok_or_else(unknown)? as *const ::swift_rs::SRObject<()>
+                        as *const ::swift_rs::SRObject<#payload>);
+                    #value
+                }}
+            }
+        };
+
+        arms.push(quote!(#tag => ::core::result::Result::Ok(#value),));
+    }
+
+    Ok(quote! {
+        const _: () = {
+            #(#payloads)*
+
+            impl ::swift_rs::SwiftEnum for #ident {
+                type Repr = ::swift_rs::SREnum<Self>;
+
+                unsafe fn from_repr(
+                    repr: Self::Repr,
+                ) -> ::core::result::Result<Self, ::swift_rs::UnknownDiscriminant> {
+                    repr.get()
+                }
+
+                unsafe fn from_swift(
+                    tag: ::swift_rs::Int,
+                    payload: ::core::option::Option<&::swift_rs::SRObject<()>>,
+                ) -> ::core::result::Result<Self, ::swift_rs::UnknownDiscriminant> {
+                    let unknown = || ::swift_rs::UnknownDiscriminant {
+                        type_name: ::core::any::type_name::<Self>(),
+                        discriminant: tag,
+                    };
+
+                    match tag {
+                        #(#arms)*
+                        _ => ::core::result::Result::Err(unknown()),
+                    }
+                }
+            }
+        };
+    })
+}
+
+fn panic_on_unknown(input: &DeriveInput) -> Result<bool> {
+    let mut panic_on_unknown = false;
+
+    for attr in &input.attrs {
+        if attr.path().is_ident("swift_enum") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("panic_on_unknown") {
+                    panic_on_unknown = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown swift_enum attribute"))
+                }
+            })?;
+        }
+    }
+
+    Ok(panic_on_unknown)
+}
+
+fn is_repr_isize(input: &DeriveInput) -> Result<bool> {
+    let mut isize = false;
+
+    for attr in &input.attrs {
+        if attr.path().is_ident("repr") {
+            attr.parse_nested_meta(|meta| {
+                isize |= meta.path.is_ident("isize");
+                Ok(())
+            })?;
+        }
+    }
+
+    Ok(isize)
+}
@@ -0,0 +1,176 @@
+use std::{env, fs, path::PathBuf};
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, LitStr, Result};
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream> {
+    let ident = &input.ident;
+
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            ident,
+            "SwiftLayout can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new_spanned(
+            ident,
+            "SwiftLayout can only be derived for structs with named fields",
+        ));
+    };
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "SwiftLayout can't be derived for generic structs",
+        ));
+    }
+    if !is_repr_c(&input)? {
+        return Err(Error::new_spanned(
+            ident,
+            "SwiftLayout requires #[repr(C)], otherwise the layout isn't stable",
+        ));
+    }
+
+    let class = swift_class(&input)?;
+    let layout_file = layout_file(&class, ident)?;
+
+    let names = fields
+        .named
+        .iter()
+        .map(|field| field.ident.as_ref().unwrap())
+        .collect::<Vec<_>>();
+    let name_strings = names.iter().map(|name| name.to_string());
+    let types = fields.named.iter().map(|field| &field.ty);
+
+    let count_message = format!(
+        "`{ident}` doesn't have the same number of fields as the Swift class `{class}` has stored properties"
+    );
+    let field_checks = names.iter().enumerate().map(|(i, name)| {
+        let message = format!(
+            "`{ident}.{name}` doesn't have the same offset and size as stored property {i} of the Swift class `{class}`"
+        );
+        quote! {
+            assert!(swift[#i].0 == rust[#i].offset && swift[#i].1 == rust[#i].size, #message);
+        }
+    });
+
+    Ok(quote! {
+        impl ::swift_rs::SwiftLayout for #ident {
+            const SWIFT_CLASS: &'static str = #class;
+
+            const FIELDS: &'static [::swift_rs::FieldLayout] = &[
+                #(::swift_rs::FieldLayout {
+                    name: #name_strings,
+                    offset: ::swift_rs::SRObject::<#ident>::DATA_OFFSET
+                        + ::core::mem::offset_of!(#ident, #names),
+                    size: ::core::mem::size_of::<#types>(),
+                },)*
+            ];
+        }
+
+        const _: () = {
+            // Offset and size of each stored property, measured by `SwiftLinker`.
+            // `None` if the Swift classes couldn't be measured for this target.
+            let swift: ::core::option::Option<&[(usize, usize)]> =
+                include!(concat!(env!("OUT_DIR"), #layout_file));
+
+            if let ::core::option::Option::Some(swift) = swift {
+                let rust = <#ident as ::swift_rs::SwiftLayout>::FIELDS;
+                assert!(swift.len() == rust.len(), #count_message);
+                #(#field_checks)*
+            }
+        };
+    })
+}
+
+fn is_repr_c(input: &DeriveInput) -> Result<bool> {
+    let mut repr_c = false;
+
+    for attr in &input.attrs {
+        if attr.path().is_ident("repr") {
+            attr.parse_nested_meta(|meta| {
+                repr_c |= meta.path.is_ident("C");
+                Ok(())
+            })?;
+        }
+    }
+
+    Ok(repr_c)
+}
+
+/// The class name from `#[swift_layout(class = "...")]`, or the struct's name.
+fn swift_class(input: &DeriveInput) -> Result<String> {
+    let mut class = input.ident.to_string();
+
+    for attr in &input.attrs {
+        if attr.path().is_ident("swift_layout") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("class") {
+                    class = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown swift_layout attribute"))
+                }
+            })?;
+        }
+    }
+
+    Ok(class)
+}
+
+/// Path of the class's layout within `OUT_DIR`,
+/// checking that `SwiftLinker` has written it.
+///
+/// Layouts are named after the class's module and name, such as `MyModule.MyClass.rs`.
+/// Classes without a module match any module, as long as only one has a class with that name.
+fn layout_file(class: &str, ident: &syn::Ident) -> Result<String> {
+    let layouts_dir = env::var_os("OUT_DIR")
+        .map(|out_dir| PathBuf::from(out_dir).join("swift-rs").join("layouts"));
+
+    let mut matches = match &layouts_dir {
+        Some(dir) if class.contains('.') => {
+            let file = format!("{class}.rs");
+            if dir.join(&file).exists() {
+                vec![file]
+            } else {
+                vec![]
+            }
+        }
+        Some(dir) => fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .filter(|file| file.ends_with(&format!(".{class}.rs")))
+            .collect::<Vec<_>>(),
+        None => vec![],
+    };
+    matches.sort();
+
+    match matches.as_slice() {
+        [file] => Ok(format!("/swift-rs/layouts/{file}")),
+        [] => Err(Error::new_spanned(
+            ident,
+            format!(
+                "No layout was found for the Swift class `{class}`. \
+                 Make sure your build script calls `SwiftLinker::with_layout_checks` \
+                 and that `{class}` is declared in one of its packages"
+            ),
+        )),
+        files => {
+            let classes = files
+                .iter()
+                .map(|file| format!("`{}`", file.trim_end_matches(".rs")))
+                .collect::<Vec<_>>();
+            Err(Error::new_spanned(
+                ident,
+                format!(
+                    "More than one Swift class is named `{class}`: {}. \
+                     Choose one with `#[swift_layout(class = \"Module.{class}\")]`",
+                    classes.join(", ")
+                ),
+            ))
+        }
+    }
+}
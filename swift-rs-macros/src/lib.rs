@@ -0,0 +1,46 @@
+//! Derive macros for [`swift-rs`](https://docs.rs/swift-rs),
+//! which should be used through its `derive` feature rather than depended on directly.
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+mod enums;
+mod layout;
+mod object;
+
+/// Checks that a `#[repr(C)]` struct has the same layout as a Swift class.
+/// See `swift_rs::SwiftLayout` for details.
+#[proc_macro_derive(SwiftLayout, attributes(swift_layout))]
+pub fn derive_swift_layout(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    layout::derive(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements `SwiftObject` and `SwiftArg` for a `#[repr(transparent)]` wrapper
+/// around another `SwiftObject`, so that it can be used in `swift!` functions.
+/// See `swift_rs::SwiftObject` for details.
+#[proc_macro_derive(SwiftObject)]
+pub fn derive_swift_object(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    object::derive(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements `SwiftEnum` and, for C-like enums, `SwiftArg` and `TryFrom<Int>`,
+/// so that the enum can be used in `swift!` functions.
+/// `#[swift_enum(panic_on_unknown)]` also implements `SwiftRet`,
+/// which panics if Swift returns a case that the enum doesn't have.
+/// See `swift_rs::SwiftEnum` for details.
+#[proc_macro_derive(SwiftEnum, attributes(swift_enum))]
+pub fn derive_swift_enum(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    enums::derive(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
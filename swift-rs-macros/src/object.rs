@@ -0,0 +1,107 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, Data, DeriveInput, Error, Fields, Member, Result, Type};
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream> {
+    let ident = &input.ident;
+
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            ident,
+            "SwiftObject can only be derived for structs",
+        ));
+    };
+    if !is_repr_transparent(&input)? {
+        return Err(Error::new_spanned(
+            ident,
+            "SwiftObject requires #[repr(transparent)], \
+             so that the struct is passed to Swift as the object it wraps",
+        ));
+    }
+
+    let (member, inner) = object_field(&data.fields).ok_or_else(|| {
+        Error::new_spanned(
+            ident,
+            "SwiftObject can only be derived for structs wrapping a single object",
+        )
+    })?;
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // SwiftArg borrows the object for the lifetime of the call
+    let mut arg_generics = input.generics.clone();
+    arg_generics.params.insert(0, parse_quote!('swift_rs_arg));
+    let arg_where_clause = arg_generics.make_where_clause();
+    arg_where_clause
+        .predicates
+        .push(parse_quote!(Self: 'swift_rs_arg));
+    arg_where_clause
+        .predicates
+        .push(parse_quote!(<#inner as ::swift_rs::SwiftObject>::Shape: 'swift_rs_arg));
+    let (arg_impl_generics, _, arg_where_clause) = arg_generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::swift_rs::SwiftObject for #ident #ty_generics #where_clause {
+            type Shape = <#inner as ::swift_rs::SwiftObject>::Shape;
+
+            fn get_object(&self) -> &::swift_rs::SRObject<Self::Shape> {
+                ::swift_rs::SwiftObject::get_object(&self.#member)
+            }
+        }
+
+        impl #arg_impl_generics ::swift_rs::SwiftArg<'swift_rs_arg> for #ident #ty_generics
+            #arg_where_clause
+        {
+            type ArgType = ::swift_rs::SwiftRef<'swift_rs_arg, Self>;
+
+            unsafe fn as_arg(&'swift_rs_arg self) -> Self::ArgType {
+                ::swift_rs::SwiftObject::swift_ref(self)
+            }
+        }
+    })
+}
+
+fn is_repr_transparent(input: &DeriveInput) -> Result<bool> {
+    let mut transparent = false;
+
+    for attr in &input.attrs {
+        if attr.path().is_ident("repr") {
+            attr.parse_nested_meta(|meta| {
+                transparent |= meta.path.is_ident("transparent");
+                Ok(())
+            })?;
+        }
+    }
+
+    Ok(transparent)
+}
+
+/// The field holding the wrapped object,
+/// which is the only field that isn't a `PhantomData`.
+fn object_field(fields: &Fields) -> Option<(Member, &Type)> {
+    let mut objects = fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| !is_phantom_data(&field.ty))
+        .map(|(i, field)| {
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(i.into()),
+            };
+            (member, &field.ty)
+        });
+
+    let object = objects.next()?;
+    objects.next().is_none().then_some(object)
+}
+
+fn is_phantom_data(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "PhantomData"),
+        _ => false,
+    }
+}
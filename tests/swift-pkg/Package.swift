@@ -26,6 +26,6 @@ let package = Package(
             name: "test-swift",
             dependencies: [.product(name: "SwiftRs", package: "SwiftRs")],
             path: ".",
-            exclude: ["test_example.rs", "test_bindings.rs"])
+            exclude: ["test_example.rs", "test_bindings.rs", "shared.rs"])
     ]
 )
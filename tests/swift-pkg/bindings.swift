@@ -0,0 +1,29 @@
+import Foundation
+import SwiftRs
+
+// Only used to check the declarations generated by `SwiftLinker::with_bindings`
+
+class BindingsObject: NSObject {
+    var value: Int
+
+    init(value: Int) {
+        self.value = value
+    }
+}
+
+@_cdecl("bindings_typed")
+func bindingsTyped(
+    name: UnsafeRawPointer /* SRString */,
+    _ count: Int,
+    fallback: UnsafeRawPointer? /* SRString? */
+) -> UnsafeMutableRawPointer? /* BindingsObject? */ {
+    return toRust(count > 0 ? BindingsObject(value: count) : nil)
+}
+
+@_cdecl("bindings_untyped")
+func bindingsUntyped(object: UnsafeRawPointer) {}
+
+@_cdecl("bindings_unsupported")
+func bindingsUnsupported(callback: @convention(c) () -> Void) {
+    callback()
+}
@@ -16,8 +16,8 @@ class IntArray: NSObject {
 }
 
 @_cdecl("get_int_array")
-func getIntArray() -> IntArray {
-    return IntArray(data: [1, 2, 3])
+func getIntArray() -> UnsafeMutableRawPointer {
+    return toRust(IntArray(data: [1, 2, 3]))
 }
 
 class ArrayStruct: NSObject {
@@ -29,8 +29,14 @@ class ArrayStruct: NSObject {
 }
 
 @_cdecl("get_array_struct")
-func getArrayStruct() -> ArrayStruct {
-    return ArrayStruct(array: [4, 5, 6])
+func getArrayStruct() -> UnsafeMutableRawPointer {
+    return toRust(ArrayStruct(array: [4, 5, 6]))
+}
+
+@_cdecl("average")
+func average(samples: UnsafeRawPointer) -> Double {
+    let samples: SRArray<Double> = fromRust(samples)
+    return samples.toArray().reduce(0, +) / Double(samples.toArray().count)
 }
 
 // SRObject
@@ -46,25 +52,165 @@ class CustomObject: NSObject {
 }
 
 @_cdecl("get_custom_object")
-func getCustomObject() -> CustomObject {
-    return CustomObject(a: 3, b: true)
+func getCustomObject() -> UnsafeMutableRawPointer {
+    return toRust(CustomObject(a: 3, b: true))
 }
 
 // SRString
 
 @_cdecl("get_greeting")
-func getGreeting(name: SRString) -> SRString {
-    return SRString("Hello \(name.toString())!")
+func getGreeting(name: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    let name: SRString = fromRust(name)
+    return toRust(SRString("Hello \(name.toString())!"))
 }
 
 @_cdecl("echo")
-func echo(string: SRString) -> SRString {
-    return string
+func echo(string: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    let string: SRString = fromRust(string)
+    return toRust(string)
+}
+
+// SRClosure
+
+@_cdecl("count_to")
+func countTo(to: Int, callback: UnsafeRawPointer) {
+    let callback: SRClosure = fromRust(callback)
+    for n in 1...to {
+        callback(SRString("count"), n)
+    }
+}
+
+// SRTask
+
+@_cdecl("async_echo")
+func asyncEcho(string: UnsafeRawPointer, completion: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    let string: SRString = fromRust(string)
+    let completion: SRClosure = fromRust(completion)
+    return toRust(SRTask(completion) {
+        try? await Task.sleep(nanoseconds: 1_000_000)
+        return string
+    })
+}
+
+// SRResult
+
+enum ValidationError: LocalizedError {
+    case empty
+
+    var errorDescription: String? {
+        return "Name can't be empty"
+    }
+}
+
+@_cdecl("validate_name")
+func validateName(name: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    let name: SRString = fromRust(name)
+    return toRust(SRResult(catching: {
+        if name.toString().isEmpty {
+            throw ValidationError.empty
+        }
+        return name
+    }))
 }
 
 // SRData
 
 @_cdecl("get_data")
-func getData() -> SRData {
-    return SRData([1, 2, 3])
+func getData() -> UnsafeMutableRawPointer {
+    return toRust(SRData([1, 2, 3]))
+}
+
+// Optional scalars
+
+@_cdecl("parse_int")
+func parseInt(string: UnsafeRawPointer, radix: SROptionalInt) -> SROptionalInt {
+    let string: SRString = fromRust(string)
+    return toRust(Int(string.toString(), radix: fromRust(radix) ?? 10))
+}
+
+// SRDictionary
+
+@_cdecl("get_scores")
+func getScores() -> UnsafeMutableRawPointer {
+    return toRust(SRDictionary([SRString("Brendan"): 3, SRString("Lucas"): 5]))
+}
+
+@_cdecl("total_score")
+func totalScore(scores: UnsafeRawPointer) -> Int {
+    let scores: SRDictionary = fromRust(scores)
+    let dictionary: [SRString: Int] = scores.toDictionary()
+    return dictionary.values.reduce(0, +)
+}
+
+// SRSet
+
+@_cdecl("add_tag")
+func addTag(tags: UnsafeRawPointer, tag: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    let tags: SRSet = fromRust(tags)
+    let tag: SRString = fromRust(tag)
+    var set: Set<SRString> = tags.toSet()
+    set.insert(tag)
+    return toRust(SRSet(set))
+}
+
+// Ownership
+
+let unretainedString = SRString("unretained")
+
+@_cdecl("consume_string")
+func consumeString(string: UnsafeRawPointer) -> Int {
+    let string = Unmanaged<SRString>.fromOpaque(string).takeRetainedValue()
+    return string.toString().count
+}
+
+@_cdecl("retained_string")
+func retainedString() -> UnsafeMutableRawPointer {
+    return Unmanaged.passRetained(SRString("retained")).toOpaque()
+}
+
+@_cdecl("unretained_string")
+func getUnretainedString() -> UnsafeMutableRawPointer {
+    return Unmanaged.passUnretained(unretainedString).toOpaque()
+}
+
+// Main thread
+
+@_cdecl("show_alert")
+func showAlert(message: UnsafeRawPointer) {
+    let message: SRString = fromRust(message)
+    precondition(Thread.isMainThread, "show_alert must be called from the main thread")
+    print(message.toString())
+}
+
+// SwiftConstructor
+
+class Config: NSObject {
+    let name: SRString
+    let retries: Int
+
+    init(name: SRString, retries: Int) {
+        self.name = name
+        self.retries = retries
+    }
+}
+
+@_cdecl("config_new")
+func configNew(name: UnsafeRawPointer, retries: Int) -> UnsafeMutableRawPointer {
+    return toRust(Config(name: fromRust(name), retries: retries))
+}
+
+// SwiftJson
+
+struct Release: Codable {
+    var version: String
+    var downloadCount: UInt64
+}
+
+@_cdecl("next_release")
+func nextRelease(release: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    var release: Release = try! fromRust(json: release)
+    let patch = Int(release.version.split(separator: ".").last!)! + 1
+    release.version = release.version.split(separator: ".").dropLast().joined(separator: ".") + ".\(patch)"
+    release.downloadCount = 0
+    return try! toRust(json: release)
 }
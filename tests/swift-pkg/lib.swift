@@ -13,17 +13,293 @@ class Complex: NSObject {
     }
 }
 
+// Objects are passed as raw pointers so that this package also builds
+// on platforms without an Objective-C runtime
+
 @_cdecl("complex_data")
-func complexData() -> SRObjectArray {
-    return SRObjectArray([
+func complexData() -> UnsafeMutableRawPointer {
+    return toRust(SRObjectArray([
         Complex(a: SRString("Brendan"), b: 0, c: true),
         Complex(a: SRString("Amod"), b: 1, c: false),
         Complex(a: SRString("Lucas"), b: 2, c: true),
         Complex(a: SRString("Oscar"), b: 3, c: false),
-    ])
+    ]))
+}
+
+@_cdecl("complex_new")
+func complexNew(a: UnsafeRawPointer, b: Int, c: Bool) -> UnsafeMutableRawPointer {
+    return toRust(Complex(a: fromRust(a), b: b, c: c))
+}
+
+struct Playlist: Codable {
+    var name: String
+    var songs: [Song]
+    var shuffle: Bool?
+}
+
+struct Song: Codable {
+    var title: String
+    var durationSeconds: Int
+}
+
+@_cdecl("sort_playlist")
+func sortPlaylist(playlist: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    var playlist: Playlist = try! fromRust(json: playlist)
+    playlist.songs.sort { $0.durationSeconds < $1.durationSeconds }
+    playlist.shuffle = false
+    return try! toRust(json: playlist)
+}
+
+class WeakHolder: NSObject {
+    let target: SRWeak
+
+    init(_ target: AnyObject) {
+        self.target = SRWeak(target)
+    }
+}
+
+@_cdecl("hold_weakly")
+func holdWeakly(target: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    return toRust(WeakHolder(fromRust(target, as: AnyObject.self)))
+}
+
+@_cdecl("sum_ints")
+func sumInts(array: UnsafeRawPointer /* SRArray<Int> */) -> Int {
+    let array: SRArray<Int> = fromRust(array)
+    return array.toArray().reduce(0, +)
+}
+
+@_cdecl("join_strings")
+func joinStrings(array: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    let array: SRArray<SRString> = fromRust(array)
+    return toRust(SRString(array.toArray().map { $0.toString() }.joined(separator: ", ")))
+}
+
+@_cdecl("sum_complex")
+func sumComplex(array: UnsafeRawPointer) -> Int {
+    let array: SRObjectArray = fromRust(array)
+    return array.toArray().map { ($0 as! Complex).b }.reduce(0, +)
+}
+
+@_cdecl("invert_dictionary")
+func invertDictionary(dictionary: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    let dictionary: SRDictionary = fromRust(dictionary)
+    let names: [Int: SRString] = dictionary.toDictionary()
+    return toRust(SRDictionary(Dictionary(uniqueKeysWithValues: names.map { ($1, $0) })))
+}
+
+@_cdecl("double_set")
+func doubleSet(set: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    let set: SRSet = fromRust(set)
+    let elements: Set<Int> = set.toSet()
+    return toRust(SRSet(Set(elements.map { $0 * 2 })))
+}
+
+@_cdecl("optional_not")
+func optionalNot(value: SROptionalBool) -> SROptionalBool {
+    return toRust(fromRust(value).map { !$0 })
+}
+
+@_cdecl("optional_half")
+func optionalHalf(value: SROptionalDouble) -> SROptionalDouble {
+    return toRust(fromRust(value).map { $0 / 2 })
+}
+
+@_cdecl("optional_negate")
+func optionalNegate(value: SROptionalInt8) -> SROptionalInt8 {
+    // nil when negating overflows
+    return toRust(fromRust(value).flatMap { $0 == Int8.min ? nil : -$0 })
+}
+
+@_cdecl("optional_echo")
+func optionalEcho(value: SROptionalUInt64) -> SROptionalUInt64 {
+    return value
+}
+
+struct TestError: Error {}
+
+@_cdecl("maybe_throw")
+func maybeThrow(shouldThrow: Bool) -> UnsafeMutableRawPointer {
+    return toRust(SRResult(catching: {
+        if shouldThrow {
+            throw TestError()
+        }
+        return nil
+    }))
+}
+
+@_cdecl("checked_divide")
+func checkedDivide(a: Int, b: Int) -> UnsafeMutableRawPointer {
+    return toRust(SRResult(catching: { () throws -> Int in
+        if b == 0 {
+            throw TestError()
+        }
+        return a / b
+    }))
+}
+
+@_cdecl("parse_double")
+func parseDouble(string: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    let string: SRString = fromRust(string)
+    return toRust(SRResult(catching: { () throws -> Double? in
+        if string.toString().isEmpty {
+            throw TestError()
+        }
+        return Double(string.toString())
+    }))
+}
+
+@_cdecl("find_word")
+func findWord(prefix: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    let prefix: SRString = fromRust(prefix)
+    return toRust(SRResult(catching: { () throws -> NSObject? in
+        if prefix.toString().isEmpty {
+            throw TestError()
+        }
+        return ["apple", "banana"].first { $0.hasPrefix(prefix.toString()) }.map { SRString($0) }
+    }))
+}
+
+var storedClosure: SRClosure?
+
+@_cdecl("store_closure")
+func storeClosure(closure: UnsafeRawPointer) {
+    storedClosure = fromRust(closure)
+}
+
+@_cdecl("call_stored_closure")
+func callStoredClosure(value: Int) {
+    storedClosure?(SRString("called"), value)
+}
+
+@_cdecl("clear_stored_closure")
+func clearStoredClosure() {
+    storedClosure = nil
 }
 
 @_cdecl("echo_data")
-func echoData(data: SRData) -> SRData {
-    return SRData(data.toArray())
+func echoData(data: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    let data: SRData = fromRust(data)
+    return toRust(SRData(data.toArray()))
+}
+
+@_cdecl("async_add")
+func asyncAdd(a: Int, b: Int, completion: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    let completion: SRClosure = fromRust(completion)
+    return toRust(SRTask(completion) {
+        try? await Task.sleep(nanoseconds: 1_000_000)
+        return a + b
+    })
+}
+
+@_cdecl("async_maybe_throw")
+func asyncMaybeThrow(shouldThrow: Bool, completion: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    let completion: SRClosure = fromRust(completion)
+    return toRust(SRTask(completion, catching: {
+        try await Task.sleep(nanoseconds: 1_000_000)
+        if shouldThrow {
+            throw TestError()
+        }
+        return SRString("done")
+    }))
+}
+
+var asyncTaskCancelled = false
+
+@_cdecl("async_wait_forever")
+func asyncWaitForever(completion: UnsafeRawPointer) -> UnsafeMutableRawPointer {
+    let completion: SRClosure = fromRust(completion)
+    asyncTaskCancelled = false
+    return toRust(SRTask(completion) {
+        while !Task.isCancelled {
+            try? await Task.sleep(nanoseconds: 1_000_000)
+        }
+        asyncTaskCancelled = true
+    })
+}
+
+@_cdecl("async_task_cancelled")
+func asyncTaskWasCancelled() -> Bool {
+    return asyncTaskCancelled
+}
+
+@_cdecl("make_shape")
+func makeShape() -> UnsafeMutableRawPointer {
+    let base = SharedShape(name: SRString("base"), points: SRArray([]), closed: false, parent: nil)
+    return toRust(SharedShape(
+        name: SRString("triangle"),
+        points: SRArray([SharedPoint(x: 0, y: 0), SharedPoint(x: 1, y: 0), SharedPoint(x: 0, y: 1)]),
+        closed: true,
+        parent: base
+    ))
+}
+
+@_cdecl("next_color")
+func nextColor(color: Int) -> Int {
+    switch SharedColor(rawValue: color)! {
+    case .red: return SharedColor.green.rawValue
+    case .green: return SharedColor.blue.rawValue
+    case .blue: return SharedColor.red.rawValue
+    }
+}
+
+@_cdecl("make_fill")
+func makeFill(kind: Int) -> UnsafeMutableRawPointer {
+    let fills: [SharedFill] = [
+        .empty,
+        .solid(.green),
+        .gradient(from: .red, to: .blue, angle: 45),
+        .pattern(name: SRString("dots"), tile: SharedShape(name: SRString("dot"), points: SRArray([]), closed: true, parent: nil)),
+    ]
+    return toRust(fills[kind])
+}
+
+@_cdecl("unknown_color")
+func unknownColor() -> Int {
+    return 3
+}
+
+/// A case that `SharedFill` doesn't have, or a solid fill with a color that `SharedColor` doesn't have.
+struct UnknownFill: SREnumRepresentable {
+    let rustTag: Int
+    let rustPayload: NSObject?
+}
+
+class UnknownColorPayload: NSObject {
+    let _0RawValue = 3
+}
+
+@_cdecl("make_unknown_fill")
+func makeUnknownFill(kind: Int) -> UnsafeMutableRawPointer {
+    let fills = [
+        UnknownFill(rustTag: 4, rustPayload: nil),
+        UnknownFill(rustTag: 1, rustPayload: UnknownColorPayload()),
+    ]
+    return toRust(fills[kind])
+}
+
+@_cdecl("make_style")
+func makeStyle() -> UnsafeMutableRawPointer {
+    return toRust(SharedStyle(stroke: .blue, fill: SREnum(.solid(.red))))
+}
+
+class Photo: NSObject {
+    var name: SRString
+    var width: Int
+
+    init(name: SRString, width: Int) {
+        self.name = name
+        self.width = width
+    }
+}
+
+@_cdecl("make_photo")
+func makePhoto(name: UnsafeRawPointer, width: Int) -> UnsafeMutableRawPointer {
+    return toRust(Photo(name: fromRust(name), width: width))
+}
+
+@_cdecl("photo_width")
+func photoWidth(photo: UnsafeRawPointer) -> Int {
+    let photo: Photo = fromRust(photo)
+    return photo.width
 }
@@ -0,0 +1,52 @@
+//! Types shared with Swift, whose Swift classes are generated into `shared.swift`
+//! by `SwiftLinker::with_swift_classes`, and checked by `SwiftLinker::with_layout_checks`.
+
+use swift_rs::{Bool, Float64, Int, SRArray, SREnum, SRObject, SRString};
+
+#[cfg_attr(all(feature = "derive", test_swift_rs), derive(swift_rs::SwiftLayout))]
+#[repr(C)]
+pub struct SharedPoint {
+    pub x: Int,
+    pub y: Int,
+}
+
+#[cfg_attr(all(feature = "derive", test_swift_rs), derive(swift_rs::SwiftLayout))]
+#[repr(C)]
+pub struct SharedShape {
+    pub name: SRString,
+    pub points: SRArray<SRObject<SharedPoint>>,
+    pub closed: Bool,
+    pub parent: Option<SRObject<SharedShape>>,
+}
+
+#[cfg_attr(feature = "derive", derive(swift_rs::SwiftEnum))]
+#[cfg_attr(feature = "derive", swift_enum(panic_on_unknown))]
+#[derive(Debug, PartialEq)]
+#[repr(isize)]
+pub enum SharedColor {
+    Red,
+    Green,
+    Blue = 10,
+}
+
+#[cfg_attr(feature = "derive", derive(swift_rs::SwiftEnum))]
+pub enum SharedFill {
+    Empty,
+    Solid(SharedColor),
+    Gradient {
+        from: SharedColor,
+        to: SharedColor,
+        angle: Float64,
+    },
+    Pattern {
+        name: SRString,
+        tile: Option<SRObject<SharedShape>>,
+    },
+}
+
+#[cfg_attr(all(feature = "derive", test_swift_rs), derive(swift_rs::SwiftLayout))]
+#[repr(C)]
+pub struct SharedStyle {
+    pub stroke: SharedColor,
+    pub fill: SREnum<SharedFill>,
+}
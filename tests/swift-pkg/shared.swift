@@ -0,0 +1,107 @@
+// Generated by swift-rs from `tests/swift-pkg/shared.rs`.
+// Any changes will be overwritten.
+
+import Foundation
+import SwiftRs
+
+public class SharedPoint: NSObject {
+    public var x: Int
+    public var y: Int
+
+    public init(x: Int, y: Int) {
+        self.x = x
+        self.y = y
+    }
+}
+
+public class SharedShape: NSObject {
+    public var name: SRString
+    public var points: SRArray<SharedPoint>
+    public var closed: Bool
+    public var parent: SharedShape?
+
+    public init(name: SRString, points: SRArray<SharedPoint>, closed: Bool, parent: SharedShape?) {
+        self.name = name
+        self.points = points
+        self.closed = closed
+        self.parent = parent
+    }
+}
+
+public enum SharedColor: Int {
+    case red = 0
+    case green = 1
+    case blue = 10
+}
+
+public enum SharedFill {
+    case empty
+    case solid(SharedColor)
+    case gradient(from: SharedColor, to: SharedColor, angle: Double)
+    case pattern(name: SRString, tile: SharedShape?)
+}
+
+extension SharedFill: SREnumRepresentable {
+    public var rustTag: Int {
+        switch self {
+        case .empty: return 0
+        case .solid: return 1
+        case .gradient: return 2
+        case .pattern: return 3
+        }
+    }
+
+    public var rustPayload: NSObject? {
+        switch self {
+        case .empty: return nil
+        case let .solid(_0): return SharedFillSolidPayload(_0: _0)
+        case let .gradient(from, to, angle): return SharedFillGradientPayload(from: from, to: to, angle: angle)
+        case let .pattern(name, tile): return SharedFillPatternPayload(name: name, tile: tile)
+        }
+    }
+}
+
+class SharedFillSolidPayload: NSObject {
+    let _0RawValue: Int
+
+    init(_0: SharedColor) {
+        self._0RawValue = _0.rawValue
+    }
+}
+
+class SharedFillGradientPayload: NSObject {
+    let fromRawValue: Int
+    let toRawValue: Int
+    let angle: Double
+
+    init(from: SharedColor, to: SharedColor, angle: Double) {
+        self.fromRawValue = from.rawValue
+        self.toRawValue = to.rawValue
+        self.angle = angle
+    }
+}
+
+class SharedFillPatternPayload: NSObject {
+    let name: SRString
+    let tile: SharedShape?
+
+    init(name: SRString, tile: SharedShape?) {
+        self.name = name
+        self.tile = tile
+    }
+}
+
+public class SharedStyle: NSObject {
+    // Stored as its raw value so that Rust can read it
+    var strokeRawValue: Int
+    public var stroke: SharedColor {
+        get { return SharedColor(rawValue: strokeRawValue)! }
+        set { strokeRawValue = newValue.rawValue }
+    }
+    public var fill: SREnum<SharedFill>
+
+    public init(stroke: SharedColor, fill: SREnum<SharedFill>) {
+        self.strokeRawValue = stroke.rawValue
+        self.fill = fill
+    }
+}
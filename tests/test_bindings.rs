@@ -4,13 +4,34 @@
 //! the test swift code to be linked.
 
 use serial_test::serial;
-use std::{env, process::Command};
+use std::{
+    collections::{BTreeMap, HashSet},
+    env,
+    future::Future,
+    io::Write,
+    pin::pin,
+    process::Command,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Wake},
+    thread,
+    time::{Duration, Instant},
+};
 use swift_rs::*;
 
+// The shared enums are only used through `#[derive(SwiftEnum)]`
+#[cfg_attr(not(feature = "derive"), allow(dead_code))]
+#[path = "swift-pkg/shared.rs"]
+mod shared;
+
+use shared::*;
+
 macro_rules! test_with_leaks {
     ( $op:expr ) => {{
         let leaks_env_var = "TEST_RUNNING_UNDER_LEAKS";
-        if env::var(leaks_env_var).unwrap_or_else(|_| "false".into()) == "true" {
+        if !cfg!(target_os = "macos") {
+            // `leaks` and `codesign` are only available on macOS
+            $op();
+        } else if env::var(leaks_env_var).unwrap_or_else(|_| "false".into()) == "true" {
             let _ = $op();
         } else {
             // we run $op directly in the current process first, as leaks will not give
@@ -104,7 +125,27 @@ fn test_autoreleasepool() {
     });
 }
 
+swift!(fn consume_string(#[owned] string: SRString) -> Int);
+swift!(fn retained_string() -> #[owned] SRString);
+swift!(fn unretained_string() -> #[unretained] SRString);
+
+#[test]
+#[serial]
+fn test_ownership() {
+    test_with_leaks!(|| {
+        // unbalanced retains would leak or crash under memory pressure
+        for _ in 0..10_000 {
+            let string: SRString = "Brendan".into();
+            assert_eq!(unsafe { consume_string(string) }, 7);
+
+            assert_eq!(unsafe { retained_string() }.as_str(), "retained");
+            assert_eq!(unsafe { unretained_string() }.as_str(), "unretained");
+        }
+    });
+}
+
 #[repr(C)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
 struct Complex {
     a: SRString,
     b: Int,
@@ -112,6 +153,13 @@ struct Complex {
 }
 
 swift!(fn complex_data() -> SRObjectArray<Complex>);
+swift!(fn complex_new(a: &SRString, b: Int, c: Bool) -> SRObject<Complex>);
+
+impl SwiftConstructor for Complex {
+    fn construct(self) -> SRObject<Self> {
+        unsafe { complex_new(&self.a, self.b, self.c) }
+    }
+}
 
 #[test]
 #[serial]
@@ -127,6 +175,187 @@ fn test_complex() {
     });
 }
 
+#[repr(C)]
+struct WeakHolder {
+    target: SRWeak<Complex>,
+}
+
+#[test]
+#[serial]
+fn test_constructor() {
+    test_with_leaks!(|| {
+        let object = SRObject::new(Complex {
+            a: "Brendan".into(),
+            b: 4,
+            c: true,
+        });
+        assert_eq!(object.a.as_str(), "Brendan");
+
+        let array = SRObjectArray::from(&[object][..]);
+        assert_eq!(unsafe { sum_complex(&array) }, 4);
+    });
+}
+
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+#[test]
+#[serial]
+fn test_deserialize() {
+    test_with_leaks!(|| {
+        let array: SRArray<SRObject<Complex>> = serde_json::from_str(
+            r#"[{ "a": "Amod", "b": 1, "c": false }, { "a": "Lucas", "b": 2, "c": true }]"#,
+        )
+        .unwrap();
+        assert_eq!(array[1].a.as_str(), "Lucas");
+        assert_eq!(array.iter().map(|complex| complex.b).sum::<Int>(), 3);
+
+        let data: SRData = serde_json::from_str("[1, 2, 3]").unwrap();
+        assert_eq!(data.as_slice(), &[1, 2, 3]);
+
+        let ints: SRArray<Int> = serde_json::from_str("[1, 2, 3]").unwrap();
+        assert_eq!(unsafe { sum_ints(&ints) }, 6);
+
+        let names: SRDictionary<Int, SRString> =
+            serde_json::from_str(r#"{ "1": "Brendan", "2": "Oscar" }"#).unwrap();
+        let ids = unsafe { invert_dictionary(&names) };
+        assert_eq!(ids.get("Brendan"), Some(&1));
+        assert_eq!(ids.get("Oscar"), Some(&2));
+
+        // Duplicates are removed, as they would be in Swift
+        let set: SRSet<Int> = serde_json::from_str("[1, 2, 2, 3]").unwrap();
+        assert_eq!(set.len(), 3);
+        let doubled = unsafe { double_set(&set) };
+        assert!(doubled.contains(&6));
+    });
+}
+
+#[cfg(feature = "json")]
+mod json {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    pub struct Playlist {
+        pub name: String,
+        pub songs: Vec<Song>,
+        pub shuffle: Option<bool>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Song {
+        pub title: String,
+        pub duration_seconds: i64,
+    }
+}
+
+#[cfg(feature = "json")]
+swift!(fn sort_playlist(playlist: &SwiftJson<json::Playlist>) -> SwiftJson<json::Playlist>);
+
+#[cfg(feature = "json")]
+#[test]
+#[serial]
+fn test_json() {
+    use json::*;
+
+    test_with_leaks!(|| {
+        let song = |title: &str, duration_seconds| Song {
+            title: title.into(),
+            duration_seconds,
+        };
+        let playlist = Playlist {
+            name: "Focus".into(),
+            songs: vec![song("Long", 300), song("Short", 120), song("Medium", 200)],
+            shuffle: None,
+        };
+
+        let json = SwiftJson::new(&playlist).unwrap();
+        let sorted = unsafe { sort_playlist(&json) }.decode().unwrap();
+
+        assert_eq!(
+            sorted,
+            Playlist {
+                name: "Focus".into(),
+                songs: vec![song("Short", 120), song("Medium", 200), song("Long", 300)],
+                shuffle: Some(false),
+            }
+        );
+    });
+}
+
+swift!(fn hold_weakly(target: &SRObject<Complex>) -> SRObject<WeakHolder>);
+
+#[test]
+#[serial]
+fn test_weak() {
+    test_with_leaks!(|| {
+        let (weak, holder) = autoreleasepool!({
+            let data = unsafe { complex_data() };
+            let object = &data[0];
+
+            let weak = object.downgrade();
+            let holder = unsafe { hold_weakly(object) };
+            assert_eq!(weak.upgrade().unwrap().a.as_str(), "Brendan");
+            assert_eq!(holder.target.upgrade().unwrap().b, 0);
+
+            (weak, holder)
+        });
+
+        // The objects were deallocated along with the array
+        assert!(weak.upgrade().is_none());
+        assert!(holder.target.upgrade().is_none());
+    });
+}
+
+#[test]
+#[serial]
+fn test_reference_counting() {
+    test_with_leaks!(|| {
+        let string = SRString::from("hello");
+        let count = string.retain_count();
+
+        let clone = string.clone();
+        assert!(SRString::ptr_eq(&string, &clone));
+        assert!(!SRString::ptr_eq(&string, &SRString::from("hello")));
+        assert_eq!(string.retain_count(), count + 1);
+
+        let ptr = clone.into_raw();
+        assert_eq!(string.retain_count(), count + 1);
+        let clone = unsafe { SRString::from_raw(ptr) };
+        assert_eq!(clone.as_str(), "hello");
+
+        let borrowed = unsafe { SRString::from_raw_retained(ptr) };
+        assert_eq!(string.retain_count(), count + 2);
+
+        drop(clone);
+        drop(borrowed);
+        assert_eq!(string.retain_count(), count);
+
+        let data = unsafe { complex_data() };
+        let array = data.clone();
+        assert!(SRArray::ptr_eq(&data, &array));
+        assert!(SRObject::ptr_eq(&data[0], &array[0].clone()));
+    });
+}
+
+#[test]
+#[serial]
+fn test_thread_safety() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<SRString>();
+    assert_send_sync::<SRData>();
+    assert_send_sync::<SRArray<SRString>>();
+    assert_send_sync::<SRDictionary<SRString, Int>>();
+    assert_send_sync::<SRSet<Int>>();
+
+    test_with_leaks!(|| {
+        let array = SRArray::from(&[SRString::from("hello")][..]);
+        let length = std::thread::spawn(move || array[0].as_str().len());
+        assert_eq!(length.join().unwrap(), 5);
+
+        let is_main = std::thread::spawn(|| MainThreadMarker::new().is_some());
+        assert!(!is_main.join().unwrap());
+    });
+}
+
 swift!(fn echo_data(data: &SRData) -> SRData);
 
 #[test]
@@ -139,6 +368,448 @@ fn test_data() {
             let data = unsafe { echo_data(&bytes.into()) };
             assert_eq!(data.as_slice(), bytes);
         }
+
+        // Rust-owned bytes aren't copied
+        let vec = bytes.repeat(1000);
+        let pointer = vec.as_ptr();
+        let data = SRData::from_vec(vec);
+        assert_eq!(data.as_ptr(), pointer);
+        assert_eq!(unsafe { echo_data(&data) }.as_slice(), data.as_slice());
+
+        let boxed = bytes.to_vec().into_boxed_slice();
+        let pointer = boxed.as_ptr();
+        let data = SRData::from(boxed);
+        assert_eq!(data.as_ptr(), pointer);
+        assert_eq!(data.as_slice(), bytes);
+    });
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+#[serial]
+fn test_bytes() {
+    test_with_leaks!(|| {
+        let data = unsafe { echo_data(&b"hello"[..].into()) };
+        let pointer = data.as_ptr();
+
+        let bytes = data.into_bytes();
+        assert_eq!(bytes.as_ptr(), pointer);
+
+        // The Swift object is kept alive by the remaining clones
+        let tail = bytes.slice(1..);
+        drop(bytes);
+        assert_eq!(&tail[..], b"ello");
+
+        let string = unsafe { echo(&"hello".into()) }.into_byte_string();
+        assert_eq!(&*string, "hello");
+    });
+}
+
+swift!(fn sum_ints(array: &SRArray<Int>) -> Int);
+
+#[test]
+#[serial]
+fn test_scalar_array() {
+    test_with_leaks!(|| {
+        for _ in 0..10_000 {
+            let array: SRArray<Int> = (1..=4).collect();
+            assert_eq!(array.as_slice(), &[1, 2, 3, 4]);
+            assert_eq!(unsafe { sum_ints(&array) }, 10);
+        }
+
+        let empty = SRArray::<Int>::from(vec![]);
+        assert!(empty.is_empty());
+    });
+}
+
+#[test]
+#[serial]
+fn test_mutable() {
+    test_with_leaks!(|| {
+        let mut array = SRMutableArray::with_capacity(2);
+        array.extend(1..=1000);
+        array.as_mut_slice()[0] = 1000;
+        array.truncate(500);
+        assert_eq!(array.len(), 500);
+        assert!(array.capacity() >= 1000);
+
+        let array = array.into_array();
+        assert_eq!(array[0], 1000);
+        assert_eq!(unsafe { sum_ints(&array) }, 999 + (1..=500).sum::<Int>());
+
+        let mut data = SRMutableData::new();
+        for i in 0..10_000 {
+            write!(data, "{i},").unwrap();
+        }
+        data.truncate(data.len() - 1);
+        let expected = (0..10_000).map(|i| i.to_string()).collect::<Vec<_>>();
+
+        let data = unsafe { echo_data(&data.into()) };
+        assert_eq!(data.as_slice(), expected.join(",").as_bytes());
+    });
+}
+
+swift!(fn join_strings(array: &SRArray<SRString>) -> SRString);
+swift!(fn sum_complex(array: &SRObjectArray<Complex>) -> Int);
+
+#[test]
+#[serial]
+fn test_object_arrays() {
+    test_with_leaks!(|| {
+        for _ in 0..10_000 {
+            let paths: SRArray<SRString> =
+                ["/a", "/b", "/c"].into_iter().map(SRString::from).collect();
+            let joined = unsafe { join_strings(&paths) };
+            assert_eq!(joined.as_str(), "/a, /b, /c");
+        }
+
+        let data = unsafe { complex_data() };
+        let copy = SRObjectArray::from(data.as_slice());
+        drop(data);
+        assert_eq!(copy[3].a.as_str(), "Oscar");
+        assert_eq!(unsafe { sum_complex(&copy) }, 6);
+    });
+}
+
+swift!(fn invert_dictionary(dictionary: &SRDictionary<Int, SRString>) -> SRDictionary<SRString, Int>);
+swift!(fn double_set(set: &SRSet<Int>) -> SRSet<Int>);
+
+#[test]
+#[serial]
+fn test_dictionaries_and_sets() {
+    test_with_leaks!(|| {
+        for _ in 0..10_000 {
+            let names: SRDictionary<Int, SRString> =
+                BTreeMap::from([(1, "Brendan".into()), (2, "Oscar".into())]).into();
+            assert_eq!(names.get(&2).map(|name| name.as_str()), Some("Oscar"));
+
+            let ids = unsafe { invert_dictionary(&names) };
+            assert_eq!(ids.len(), 2);
+            assert_eq!(ids.get("Brendan"), Some(&1));
+            assert_eq!(ids.get("Oscar"), Some(&2));
+            assert!(!ids.contains_key("Lucas"));
+        }
+
+        let set: SRSet<Int> = HashSet::from([1, 2, 3]).into();
+        let doubled = unsafe { double_set(&set) };
+        let mut elements = doubled.as_slice().to_vec();
+        elements.sort();
+        assert_eq!(elements, [2, 4, 6]);
+        assert!(doubled.contains(&4));
+        assert!(!doubled.contains(&3));
+
+        let empty = SRSet::<SRString>::from(HashSet::new());
+        assert!(empty.is_empty());
+    });
+}
+
+swift!(fn optional_not(value: Option<Bool>) -> Option<Bool>);
+swift!(fn optional_half(value: Option<Float64>) -> Option<Float64>);
+swift!(fn optional_negate(value: Option<Int8>) -> Option<Int8>);
+swift!(fn optional_echo(value: Option<UInt64>) -> Option<UInt64>);
+
+#[test]
+#[serial]
+fn test_optional_scalars() {
+    unsafe {
+        assert_eq!(optional_not(Some(true)), Some(false));
+        assert_eq!(optional_not(None), None);
+
+        assert_eq!(optional_half(Some(5.0)), Some(2.5));
+        assert_eq!(optional_half(None), None);
+
+        assert_eq!(optional_negate(Some(3)), Some(-3));
+        assert_eq!(optional_negate(Some(Int8::MIN)), None);
+        assert_eq!(optional_negate(None), None);
+
+        assert_eq!(optional_echo(Some(UInt64::MAX)), Some(UInt64::MAX));
+        assert_eq!(optional_echo(Some(0)), Some(0));
+        assert_eq!(optional_echo(None), None);
+    }
+}
+
+swift!(fn maybe_throw(should_throw: Bool) -> Result<(), SwiftError>);
+
+#[test]
+#[serial]
+fn test_result() {
+    test_with_leaks!(|| {
+        for _ in 0..10_000 {
+            assert!(unsafe { maybe_throw(false) }.is_ok());
+
+            let error = unsafe { maybe_throw(true) }.unwrap_err();
+            assert_eq!(error.type_name, "test_swift.TestError");
+        }
+    });
+}
+
+swift!(fn checked_divide(a: Int, b: Int) -> Result<Int, SwiftError>);
+swift!(fn parse_double(string: &SRString) -> Result<Option<Float64>, SwiftError>);
+swift!(fn find_word(prefix: &SRString) -> Result<Option<SRString>, SwiftError>);
+
+#[test]
+#[serial]
+fn test_result_values() {
+    test_with_leaks!(|| {
+        for _ in 0..10_000 {
+            assert_eq!(unsafe { checked_divide(7, 2) }, Ok(3));
+            assert!(unsafe { checked_divide(1, 0) }.is_err());
+
+            assert_eq!(unsafe { parse_double(&"1.5".into()) }, Ok(Some(1.5)));
+            assert_eq!(unsafe { parse_double(&"one".into()) }, Ok(None));
+            assert!(unsafe { parse_double(&"".into()) }.is_err());
+
+            let word = unsafe { find_word(&"ban".into()) }.unwrap();
+            assert_eq!(word.as_ref().map(|word| word.as_str()), Some("banana"));
+            assert!(unsafe { find_word(&"cherry".into()) }.unwrap().is_none());
+            assert!(unsafe { find_word(&"".into()) }.is_err());
+        }
+    });
+}
+
+swift!(fn store_closure(closure: &SRClosure<(SRString, Int)>));
+swift!(fn call_stored_closure(value: Int));
+swift!(fn clear_stored_closure());
+
+#[test]
+#[serial]
+fn test_closure() {
+    test_with_leaks!(|| {
+        let calls = Arc::new(Mutex::new(vec![]));
+
+        let closure = {
+            let calls = calls.clone();
+            SRClosure::new(move |name: SRString, value: Int| {
+                calls.lock().unwrap().push(format!("{name} {value}"));
+            })
+        };
+
+        unsafe { store_closure(&closure) };
+        // Swift keeps the closure alive after Rust is done with it
+        drop(closure);
+
+        for i in 0..3 {
+            unsafe { call_stored_closure(i) };
+        }
+        assert_eq!(*calls.lock().unwrap(), ["called 0", "called 1", "called 2"]);
+
+        unsafe { clear_stored_closure() };
+        assert_eq!(Arc::strong_count(&calls), 1);
+    });
+}
+
+swift!(async fn async_add(a: Int, b: Int) -> Int);
+swift!(async fn async_maybe_throw(should_throw: Bool) -> Result<SRString, SwiftError>);
+swift!(async fn async_wait_forever());
+swift!(fn async_task_cancelled() -> Bool);
+
+/// Minimal executor, since [`SwiftFuture`] doesn't depend on a particular one.
+fn block_on<F: Future>(future: F) -> F::Output {
+    struct ThreadWaker(thread::Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark()
+        }
+    }
+
+    let waker = Arc::new(ThreadWaker(thread::current())).into();
+    let mut future = pin!(future);
+    loop {
+        match future.as_mut().poll(&mut Context::from_waker(&waker)) {
+            Poll::Ready(value) => return value,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[test]
+#[serial]
+fn test_async() {
+    test_with_leaks!(|| {
+        assert_eq!(block_on(unsafe { async_add(1, 2) }), 3);
+
+        let value = block_on(unsafe { async_maybe_throw(false) }).unwrap();
+        assert_eq!(value.as_str(), "done");
+
+        let error = block_on(unsafe { async_maybe_throw(true) }).err().unwrap();
+        assert_eq!(error.type_name, "test_swift.TestError");
+
+        // Futures can be polled from a different thread than the one that created them
+        fn assert_send<T: Send>(value: T) -> T {
+            value
+        }
+        let future = assert_send(unsafe { async_add(2, 3) });
+        assert_eq!(thread::spawn(|| block_on(future)).join().unwrap(), 5);
+
+        // Dropping the future cancels the Swift task
+        drop(unsafe { async_wait_forever() });
+        let start = Instant::now();
+        while !unsafe { async_task_cancelled() } {
+            assert!(
+                start.elapsed() < Duration::from_secs(5),
+                "task wasn't cancelled"
+            );
+            thread::sleep(Duration::from_millis(1));
+        }
+    });
+}
+
+#[test]
+fn test_generated_bindings() {
+    let bindings = std::fs::read_to_string(concat!(env!("OUT_DIR"), "/swift_bindings.rs")).unwrap();
+
+    for declaration in [
+        "swift_rs::swift!(pub fn sum_ints(array: &swift_rs::SRArray<swift_rs::Int>) -> swift_rs::Int);",
+        "swift_rs::swift!(pub fn optional_half(value: Option<swift_rs::Float64>) -> Option<swift_rs::Float64>);",
+        "swift_rs::swift!(pub fn bindings_typed(name: &swift_rs::SRString, count: swift_rs::Int, \
+         fallback: Option<&swift_rs::SRString>) -> Option<swift_rs::SRObject<BindingsObject>>);",
+    ] {
+        assert!(bindings.contains(declaration), "missing {declaration}");
+    }
+
+    // Functions with unsupported types or unannotated pointers are skipped
+    assert!(!bindings.contains("bindings_unsupported"));
+    assert!(!bindings.contains("bindings_untyped"));
+    assert!(!bindings.contains("maybe_throw"));
+    // as are the internal functions of SwiftRs, which is a dependency
+    assert!(!bindings.contains("retain_object"));
+}
+
+swift!(fn make_shape() -> SRObject<SharedShape>);
+
+#[test]
+#[serial]
+fn test_generated_classes() {
+    test_with_leaks!(|| {
+        let shape = unsafe { make_shape() };
+        assert_eq!(shape.name.as_str(), "triangle");
+        assert!(shape.closed);
+
+        let points = shape
+            .points
+            .iter()
+            .map(|point| (point.x, point.y))
+            .collect::<Vec<_>>();
+        assert_eq!(points, [(0, 0), (1, 0), (0, 1)]);
+
+        let parent = shape.parent.as_ref().unwrap();
+        assert_eq!(parent.name.as_str(), "base");
+        assert!(parent.parent.is_none());
+    });
+}
+
+#[cfg(feature = "derive")]
+mod enums {
+    use super::shared::*;
+    use swift_rs::{swift, Int, SRObject, UnknownDiscriminant};
+
+    swift!(pub fn next_color(color: SharedColor) -> SharedColor);
+    swift!(pub fn unknown_color() -> Result<SharedColor, UnknownDiscriminant>);
+    swift!(pub fn make_fill(kind: Int) -> Result<SharedFill, UnknownDiscriminant>);
+    swift!(pub fn make_unknown_fill(kind: Int) -> Result<SharedFill, UnknownDiscriminant>);
+    swift!(pub fn make_style() -> SRObject<SharedStyle>);
+}
+
+#[cfg(feature = "derive")]
+#[test]
+#[serial]
+fn test_enums() {
+    use enums::*;
+
+    test_with_leaks!(|| {
+        assert_eq!(unsafe { next_color(SharedColor::Green) }, SharedColor::Blue);
+        assert_eq!(unsafe { next_color(SharedColor::Blue) }, SharedColor::Red);
+
+        assert_eq!(SharedColor::try_from(10), Ok(SharedColor::Blue));
+        let error = SharedColor::try_from(3).unwrap_err();
+        assert_eq!(error.discriminant, 3);
+        let error = unsafe { unknown_color() }.unwrap_err();
+        assert_eq!(error.discriminant, 3);
+
+        assert!(matches!(unsafe { make_fill(0) }, Ok(SharedFill::Empty)));
+        assert!(matches!(
+            unsafe { make_fill(1) },
+            Ok(SharedFill::Solid(SharedColor::Green))
+        ));
+        let Ok(SharedFill::Gradient { from, to, angle }) = (unsafe { make_fill(2) }) else {
+            panic!("expected a gradient");
+        };
+        assert_eq!(
+            (from, to, angle),
+            (SharedColor::Red, SharedColor::Blue, 45.0)
+        );
+
+        for _ in 0..10_000 {
+            let Ok(SharedFill::Pattern { name, tile }) = (unsafe { make_fill(3) }) else {
+                panic!("expected a pattern");
+            };
+            assert_eq!(name.as_str(), "dots");
+            assert_eq!(tile.unwrap().name.as_str(), "dot");
+        }
+
+        // An unknown case, and a known case holding an unknown color
+        let error = unsafe { make_unknown_fill(0) }.err().unwrap();
+        assert_eq!(
+            (error.type_name.ends_with("SharedFill"), error.discriminant),
+            (true, 4)
+        );
+        let error = unsafe { make_unknown_fill(1) }.err().unwrap();
+        assert_eq!(
+            (error.type_name.ends_with("SharedColor"), error.discriminant),
+            (true, 3)
+        );
+
+        let style = unsafe { make_style() };
+        assert_eq!(style.stroke, SharedColor::Blue);
+        assert!(matches!(
+            style.fill.get(),
+            Ok(SharedFill::Solid(SharedColor::Red))
+        ));
+    });
+}
+
+#[cfg(all(feature = "derive", test_swift_rs))]
+#[test]
+fn test_swift_layout() {
+    // Mismatched layouts fail to compile, so this only checks what was derived
+    assert_eq!(<SharedShape as SwiftLayout>::SWIFT_CLASS, "SharedShape");
+
+    let fields = <SharedShape as SwiftLayout>::FIELDS
+        .iter()
+        .map(|field| field.name)
+        .collect::<Vec<_>>();
+    assert_eq!(fields, ["name", "points", "closed", "parent"]);
+}
+
+#[cfg(feature = "derive")]
+mod photo {
+    use swift_rs::{swift, Int, SRObject, SRString, SwiftObject};
+
+    #[repr(C)]
+    pub struct PhotoData {
+        pub name: SRString,
+        pub width: Int,
+    }
+
+    #[derive(SwiftObject)]
+    #[repr(transparent)]
+    pub struct Photo(pub SRObject<PhotoData>);
+
+    swift!(pub fn make_photo(name: &SRString, width: Int) -> Photo);
+    swift!(pub fn photo_width(photo: &Photo) -> Int);
+}
+
+#[cfg(feature = "derive")]
+#[test]
+#[serial]
+fn test_derive_swift_object() {
+    use photo::*;
+
+    test_with_leaks!(|| {
+        let photo = unsafe { make_photo(&"beach.png".into(), 640) };
+        assert_eq!(photo.0.name.as_str(), "beach.png");
+        assert_eq!(unsafe { photo_width(&photo) }, 640);
     });
 }
 